@@ -0,0 +1,70 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"os"
+	"time"
+
+	"github.com/JacobSoderblom/krypin/internal/core"
+	"github.com/JacobSoderblom/krypin/internal/event"
+	"github.com/JacobSoderblom/krypin/internal/record"
+	"github.com/JacobSoderblom/krypin/pkg/timestamp"
+)
+
+// krypin-replay publishes a bus recording made with KRYPIN_BUS_RECORD to the
+// broker in MQTT_BROKER. Event timestamps are rewritten to the time of replay.
+func main() {
+	path := flag.String("file", "", "path to the recording")
+	speed := flag.Float64("speed", 1, "replay speed, 2 replays twice as fast and 0 as fast as possible")
+	flag.Parse()
+
+	if *path == "" || *speed < 0 {
+		flag.Usage()
+		os.Exit(2)
+	}
+
+	logger := core.NewLogger("replay")
+	errLogger := core.NewErrorLogger("replay")
+
+	f, err := os.Open(*path)
+	if err != nil {
+		errLogger.Log("error", err)
+		os.Exit(1)
+	}
+	defer f.Close()
+
+	client := core.OpenMqtt()
+	defer client.Disconnect(250)
+
+	var previous time.Time
+	count := 0
+
+	err = record.Read(f, func(e record.Entry) error {
+		if !previous.IsZero() && *speed > 0 {
+			time.Sleep(time.Duration(float64(e.Timestamp.Sub(previous)) / *speed))
+		}
+		previous = e.Timestamp
+
+		payload := e.Payload
+
+		if ev := event.Parse(e.Payload, e.Topic); ev != nil {
+			ev.Timestamp = timestamp.Now()
+			payload = ev.Bytes()
+		}
+
+		if t := client.Publish(e.Topic, 0, false, payload); t.Wait() && t.Error() != nil {
+			return fmt.Errorf("could not publish to %s: %w", e.Topic, t.Error())
+		}
+
+		count++
+
+		return nil
+	})
+	if err != nil {
+		errLogger.Log("error", err)
+		os.Exit(1)
+	}
+
+	logger.Log("status", "done", "messages", count)
+}
@@ -0,0 +1,19 @@
+package main
+
+import (
+	"github.com/JacobSoderblom/krypin/internal/core"
+	"github.com/labstack/echo/v4"
+)
+
+func setupAdmin() {
+	g := apigroup.Group("/admin")
+
+	g.GET("/log_level", core.GetLogLevel())
+	g.PUT("/log_level", core.SetLogLevel())
+
+	g.GET("/streams", func(c echo.Context) error {
+		return c.JSON(200, map[string]interface{}{
+			"data": wsrouter.Streams(),
+		})
+	})
+}
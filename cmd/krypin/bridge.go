@@ -0,0 +1,52 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"strings"
+
+	"github.com/JacobSoderblom/krypin/internal/bridge"
+	"github.com/JacobSoderblom/krypin/pkg/protocol/mqtt"
+)
+
+// setupBridge mirrors the topic filters in KRYPIN_BRIDGE_TOPICS (comma
+// separated, default krypin/#) to the broker in KRYPIN_BRIDGE_BROKER
+func setupBridge() {
+	broker := os.Getenv("KRYPIN_BRIDGE_BROKER")
+	if broker == "" {
+		return
+	}
+
+	if mqttclient == nil {
+		err := fmt.Errorf("KRYPIN_BRIDGE_BROKER requires MQTT_BROKER to be set")
+		errlogger.Log("error", err)
+		panic(err)
+	}
+
+	if broker == os.Getenv("MQTT_BROKER") {
+		err := fmt.Errorf("KRYPIN_BRIDGE_BROKER must differ from MQTT_BROKER, mirroring to the same broker would loop")
+		errlogger.Log("error", err)
+		panic(err)
+	}
+
+	topics := []string{"krypin/#"}
+
+	if val := os.Getenv("KRYPIN_BRIDGE_TOPICS"); val != "" {
+		topics = []string{}
+
+		for _, t := range strings.Split(val, ",") {
+			if t = strings.TrimSpace(t); t != "" {
+				topics = append(topics, t)
+			}
+		}
+	}
+
+	secondary, err := mqtt.New(mqtt.NewClientOptions(broker, "krypin-bridge"))
+	if err != nil {
+		errlogger.Log("error", err)
+		panic(err)
+	}
+
+	b := bridge.New(mqttclient, secondary, topics, errlogger)
+	module.Add(b.Execute, b.Interrupt)
+}
@@ -19,8 +19,10 @@ func setupDevicereg() {
 	devicerep := repository.NewDevice(db)
 	deviceregsvc := service.NewDeviceService(devicerep)
 
-	pubsubHandlers := transport.PubSubHandlers(deviceregsvc, socket)
-	module.Add(pubsub_transport.NewPubSubActor(pubsubHandlers, mqttclient, db, errlogger))
+	if mqttclient != nil {
+		pubsubHandlers := transport.PubSubHandlers(deviceregsvc, socket)
+		module.Add(pubsub_transport.NewPubSubActor(pubsubHandlers, mqttclient, db, errlogger, pubsubOptions()...))
+	}
 
 	deviceEndpoints := transport.Endpoints(deviceregsvc)
 	apigroup = http_transport.NewHttpHandler(deviceEndpoints, apigroup, db)
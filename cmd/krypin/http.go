@@ -0,0 +1,66 @@
+package main
+
+import (
+	"fmt"
+	"net"
+	"os"
+	"strings"
+
+	"github.com/JacobSoderblom/krypin/pkg/log"
+	"github.com/labstack/echo/v4"
+)
+
+// basePath returns the prefix all routes are served under, e.g. /krypin when
+// running behind a reverse proxy on a sub path. A missing leading slash is
+// added.
+func basePath() string {
+	path := strings.Trim(os.Getenv("KRYPIN_BASE_PATH"), "/")
+	if path == "" {
+		return ""
+	}
+
+	return "/" + path
+}
+
+// ipExtractor returns an extractor that only trusts X-Forwarded-For when the
+// request comes through one of the proxies in KRYPIN_TRUSTED_PROXIES (comma
+// separated CIDRs). Without it the address of the peer is used, since echo's
+// default extraction trusts the headers from any client.
+func ipExtractor() (echo.IPExtractor, error) {
+	val := os.Getenv("KRYPIN_TRUSTED_PROXIES")
+	if val == "" {
+		return echo.ExtractIPDirect(), nil
+	}
+
+	// echo trusts loopback, link-local and private ranges by default, which
+	// on a home network is nearly every client
+	options := []echo.TrustOption{
+		echo.TrustLoopback(false),
+		echo.TrustLinkLocal(false),
+		echo.TrustPrivateNet(false),
+	}
+
+	for _, cidr := range strings.Split(val, ",") {
+		_, ipNet, err := net.ParseCIDR(strings.TrimSpace(cidr))
+		if err != nil {
+			return nil, fmt.Errorf("invalid CIDR (%s) in KRYPIN_TRUSTED_PROXIES: %w", cidr, err)
+		}
+
+		options = append(options, echo.TrustIPRange(ipNet))
+	}
+
+	return echo.ExtractIPFromXFFHeader(options...), nil
+}
+
+// requestLogger logs every HTTP request with the client IP
+func requestLogger(logger log.Logger) echo.MiddlewareFunc {
+	return func(next echo.HandlerFunc) echo.HandlerFunc {
+		return func(c echo.Context) error {
+			err := next(c)
+
+			logger.Log("method", c.Request().Method, "path", c.Request().URL.Path, "status", c.Response().Status, "remote_ip", c.RealIP())
+
+			return err
+		}
+	}
+}
@@ -0,0 +1,72 @@
+package main
+
+import (
+	"net/http/httptest"
+	"os"
+	"testing"
+
+	"github.com/labstack/echo/v4"
+	"github.com/stretchr/testify/assert"
+)
+
+func TestIPExtractorTrustsOnlyConfiguredProxies(t *testing.T) {
+	assert := assert.New(t)
+
+	os.Setenv("KRYPIN_TRUSTED_PROXIES", "10.0.0.5/32")
+	defer os.Unsetenv("KRYPIN_TRUSTED_PROXIES")
+
+	extract, err := ipExtractor()
+	assert.Nil(err)
+
+	req := httptest.NewRequest("GET", "/", nil)
+	req.Header.Set(echo.HeaderXForwardedFor, "203.0.113.7")
+
+	req.RemoteAddr = "10.0.0.5:4321"
+	assert.Equal("203.0.113.7", extract(req))
+
+	req.RemoteAddr = "10.0.0.6:4321"
+	assert.Equal("10.0.0.6", extract(req))
+
+	req.RemoteAddr = "127.0.0.1:4321"
+	assert.Equal("127.0.0.1", extract(req))
+}
+
+func TestIPExtractorRejectsInvalidCIDR(t *testing.T) {
+	os.Setenv("KRYPIN_TRUSTED_PROXIES", "10.0.0.5")
+	defer os.Unsetenv("KRYPIN_TRUSTED_PROXIES")
+
+	_, err := ipExtractor()
+	assert.NotNil(t, err)
+}
+
+func TestIPExtractorWithoutProxiesUsesPeer(t *testing.T) {
+	assert := assert.New(t)
+
+	os.Unsetenv("KRYPIN_TRUSTED_PROXIES")
+
+	extract, err := ipExtractor()
+	assert.Nil(err)
+
+	req := httptest.NewRequest("GET", "/", nil)
+	req.Header.Set(echo.HeaderXForwardedFor, "203.0.113.7")
+	req.Header.Set(echo.HeaderXRealIP, "203.0.113.8")
+	req.RemoteAddr = "192.168.1.20:4321"
+
+	assert.Equal("192.168.1.20", extract(req))
+}
+
+func TestBasePath(t *testing.T) {
+	assert := assert.New(t)
+	defer os.Unsetenv("KRYPIN_BASE_PATH")
+
+	for val, expected := range map[string]string{
+		"":         "",
+		"/":        "",
+		"krypin":   "/krypin",
+		"/krypin/": "/krypin",
+		"/a/b":     "/a/b",
+	} {
+		os.Setenv("KRYPIN_BASE_PATH", val)
+		assert.Equal(expected, basePath(), val)
+	}
+}
@@ -2,12 +2,14 @@ package main
 
 import (
 	"context"
+	"net/http"
 	"os"
 
 	"github.com/JacobSoderblom/krypin/internal/core"
 	"github.com/JacobSoderblom/krypin/internal/database"
 	"github.com/JacobSoderblom/krypin/pkg/log"
 	"github.com/JacobSoderblom/krypin/pkg/protocol/mqtt"
+	"github.com/JacobSoderblom/krypin/pkg/spa"
 	"github.com/JacobSoderblom/krypin/pkg/websocket"
 	"github.com/labstack/echo/v4"
 	"gopkg.in/olahol/melody.v1"
@@ -36,7 +38,13 @@ func main() {
 	logger = core.NewLogger("core")
 	errlogger = core.NewErrorLogger("core")
 
-	mqttclient = core.OpenMqtt()
+	// Without MQTT_BROKER the hub runs storage only, states and devices can
+	// then only be read through the API
+	if os.Getenv("MQTT_BROKER") != "" {
+		mqttclient = core.OpenMqtt()
+	} else {
+		logger.Log("status", "MQTT_BROKER is not set, running without a bus")
+	}
 
 	var err error
 	db, err = database.Connect(ctx)
@@ -48,25 +56,47 @@ func main() {
 	module = core.NewModule("core", errlogger, logger)
 
 	e := echo.New()
+
+	e.IPExtractor, err = ipExtractor()
+	if err != nil {
+		errlogger.Log("error", err)
+		panic(err)
+	}
+
+	e.Use(requestLogger(logger))
+
+	root := e.Group(basePath())
+
 	m := melody.New()
-	socket = m
 	wsrouter = websocket.NewRouter(m)
+	socket = wsrouter
 
 	wsrouter.Use(websocket.Logger(logger), database.SocketTransaction(db))
 
-	e.GET("/ws", func(c echo.Context) error {
+	root.GET("/ws", func(c echo.Context) error {
 		m.HandleRequest(c.Response(), c.Request())
 		return nil
 	})
 
-	apigroup = e.Group("/api")
+	if dir := os.Getenv("KRYPIN_UI_DIR"); dir != "" {
+		root.GET("/ui", func(c echo.Context) error {
+			return c.Redirect(http.StatusMovedPermanently, c.Request().URL.Path+"/")
+		})
+		root.GET("/ui/*", spa.Handler(dir))
+	}
+
+	apigroup = root.Group("/api")
 
+	setupAdmin()
 	setupDevicereg()
+	setupBridge()
 
 	m.HandleMessage(wsrouter.Handler)
 	m.HandleConnect(wsrouter.HandleConnect)
 	m.HandleDisconnect(wsrouter.HandleDisconnect)
 
+	setupRecord()
+
 	module.Add(func(ctx context.Context) error {
 		return e.Start(":3000")
 	}, func(err error) {
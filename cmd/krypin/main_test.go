@@ -0,0 +1,65 @@
+package main
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"testing"
+
+	"github.com/JacobSoderblom/krypin/internal/core"
+	"github.com/JacobSoderblom/krypin/pkg/websocket"
+	"github.com/labstack/echo/v4"
+	"github.com/stretchr/testify/assert"
+	"gopkg.in/olahol/melody.v1"
+)
+
+// setupWithoutBus wires the hub the way main does when MQTT_BROKER is unset
+func setupWithoutBus() *echo.Echo {
+	logger = core.NewLogger("test")
+	errlogger = core.NewErrorLogger("test")
+	mqttclient = nil
+	db = nil
+
+	module = core.NewModule("test", errlogger, logger)
+
+	e := echo.New()
+
+	wsrouter = websocket.NewRouter(melody.New())
+	socket = wsrouter
+
+	apigroup = e.Group("/api")
+
+	setupAdmin()
+	setupDevicereg()
+	setupBridge()
+	setupRecord()
+
+	return e
+}
+
+func TestHTTPAPIWithoutBus(t *testing.T) {
+	assert := assert.New(t)
+
+	e := setupWithoutBus()
+
+	for _, path := range []string{"/api/admin/log_level", "/api/admin/streams"} {
+		rec := httptest.NewRecorder()
+		e.ServeHTTP(rec, httptest.NewRequest(http.MethodGet, path, nil))
+
+		assert.Equal(http.StatusOK, rec.Code, path)
+	}
+}
+
+func TestBusFeaturesRequireBus(t *testing.T) {
+	assert := assert.New(t)
+
+	for _, env := range []string{"KRYPIN_BUS_RECORD", "KRYPIN_BRIDGE_BROKER"} {
+		os.Setenv(env, "value")
+
+		assert.Panics(func() {
+			setupWithoutBus()
+		}, env)
+
+		os.Unsetenv(env)
+	}
+}
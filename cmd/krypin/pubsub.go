@@ -0,0 +1,23 @@
+package main
+
+import (
+	"os"
+	"strconv"
+
+	"github.com/JacobSoderblom/krypin/internal/pubsub"
+)
+
+func pubsubOptions() []pubsub.Option {
+	options := []pubsub.Option{}
+
+	if val := os.Getenv("MQTT_MAX_PAYLOAD_SIZE"); val != "" {
+		size, err := strconv.Atoi(val)
+		if err != nil || size <= 0 {
+			errlogger.Log("error", "MQTT_MAX_PAYLOAD_SIZE must be a positive integer, using default", "value", val)
+		} else {
+			options = append(options, pubsub.WithMaxPayloadSize(size))
+		}
+	}
+
+	return options
+}
@@ -0,0 +1,26 @@
+package main
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/JacobSoderblom/krypin/internal/record"
+)
+
+// setupRecord records every krypin bus message to the file in
+// KRYPIN_BUS_RECORD
+func setupRecord() {
+	path := os.Getenv("KRYPIN_BUS_RECORD")
+	if path == "" {
+		return
+	}
+
+	if mqttclient == nil {
+		err := fmt.Errorf("KRYPIN_BUS_RECORD requires MQTT_BROKER to be set")
+		errlogger.Log("error", err)
+		panic(err)
+	}
+
+	recorder := record.NewRecorder(mqttclient, path, errlogger)
+	module.Add(recorder.Execute, recorder.Interrupt)
+}
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS entity_states_entity_id_created_at_idx, device_entities_device_id_idx;
@@ -0,0 +1,5 @@
+CREATE INDEX IF NOT EXISTS entity_states_entity_id_created_at_idx
+    ON entity_states (entity_id, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS device_entities_device_id_idx
+    ON device_entities (device_id);
@@ -4,6 +4,7 @@ import (
 	"errors"
 	"reflect"
 	"strings"
+	"time"
 
 	"github.com/JacobSoderblom/krypin/pkg/timestamp"
 	"github.com/gofrs/uuid"
@@ -138,6 +139,28 @@ func (s *EntityState) Merge(val interface{}) error {
 	return nil
 }
 
+// HistoryQuery selects a window of an entity's state history. Since is
+// inclusive and Until exclusive. The zero value selects every state, oldest
+// first, without a limit.
+type HistoryQuery struct {
+	Since      *time.Time
+	Until      *time.Time
+	Descending bool
+	Limit      uint64
+}
+
+// EntityStateStats summarizes the numeric values of an entity's states over a
+// window. Min, Max, Avg, First and Last are nil when no state had a numeric value.
+type EntityStateStats struct {
+	Count   int64    `json:"count"`
+	Skipped int64    `json:"skipped"`
+	Min     *float64 `json:"min"`
+	Max     *float64 `json:"max"`
+	Avg     *float64 `json:"avg"`
+	First   *float64 `json:"first"`
+	Last    *float64 `json:"last"`
+}
+
 func UniqueEntityID(id string) string {
 	uniqueID := strings.ReplaceAll(id, "-", "_")
 	uniqueID = strings.ReplaceAll(uniqueID, " ", "_")
@@ -9,13 +9,45 @@ import (
 	"github.com/lucasb-eyer/go-colorful"
 )
 
-// Light describes the entity for light and bulbs
+// Light describes the entity for light and bulbs. ColorMode tells which
+// color control is active and should be set by modules whenever the light
+// supports more than one, since the color values alone only tell what was
+// last set.
 type Light struct {
 	Brightness int        `json:"brightness"`
 	HSL        string     `json:"hsl"`
 	IsOn       bool       `json:"is_on"`
 	ColorTemp  *ColorTemp `json:"color_temp"`
 	Mode       string     `json:"mode"`
+	ColorMode  string     `json:"color_mode,omitempty"`
+}
+
+// Light color modes
+var (
+	ColorModeTemperature string = "color_temp"
+	ColorModeRGB                = "rgb"
+	ColorModeHS                 = "hs"
+	ColorModeXY                 = "xy"
+	ColorModeWhite              = "white"
+)
+
+// ActiveColorMode returns ColorMode if set, otherwise infers the mode from
+// which color values are present. Modules set ColorMode to it before adding
+// the state so that clients always get a mode.
+func (l Light) ActiveColorMode() string {
+	if l.ColorMode != "" {
+		return l.ColorMode
+	}
+
+	if l.HSL != "" {
+		return ColorModeHS
+	}
+
+	if l.ColorTemp != nil && l.ColorTemp.Value > 0 {
+		return ColorModeTemperature
+	}
+
+	return ColorModeWhite
 }
 
 // NewLight creates a new light entity
@@ -0,0 +1,18 @@
+package entity_test
+
+import (
+	"testing"
+
+	"github.com/JacobSoderblom/krypin/devicereg/entity"
+	"github.com/stretchr/testify/assert"
+)
+
+func TestActiveColorMode(t *testing.T) {
+	assert := assert.New(t)
+
+	assert.Equal(entity.ColorModeRGB, entity.Light{ColorMode: entity.ColorModeRGB, HSL: "hsl(100, 50%, 50%)"}.ActiveColorMode())
+	assert.Equal(entity.ColorModeHS, entity.Light{HSL: "hsl(100, 50%, 50%)"}.ActiveColorMode())
+	assert.Equal(entity.ColorModeTemperature, entity.Light{ColorTemp: &entity.ColorTemp{Value: 300}}.ActiveColorMode())
+	assert.Equal(entity.ColorModeWhite, entity.Light{ColorTemp: &entity.ColorTemp{}}.ActiveColorMode())
+	assert.Equal(entity.ColorModeWhite, entity.Light{Brightness: 40}.ActiveColorMode())
+}
@@ -25,6 +25,7 @@ var (
 	SignalStrength        = "signal_strength"
 	Temperature           = "temperature"
 	Power                 = "power"
+	Energy                = "energy"
 	Pressure              = "pressure"
 	Time                  = "timestamp"
 )
@@ -11,6 +11,7 @@ var (
 	Timestamp               = "ISO8601"
 	Watt                    = "W"
 	Kilowatt                = "kW"
+	WattHour                = "Wh"
 	Hectopascal             = "hPa"
 	Millibar                = "mbar"
 )
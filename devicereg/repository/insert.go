@@ -57,6 +57,27 @@ func (r *deviceRepository) Insert(ctx context.Context, d *devicereg.Device) (*de
 	return fromDeviceModel(model), nil
 }
 
+func (r *deviceRepository) InsertEntities(ctx context.Context, deviceID uuid.UUID, entities []*devicereg.Entity) ([]*devicereg.Entity, error) {
+	models := []*deviceEntity{}
+
+	for _, e := range entities {
+		models = append(models, toEntityModel(e))
+	}
+
+	inserted, err := r.insertEntities(ctx, deviceID, models)
+	if err != nil {
+		return nil, err
+	}
+
+	res := []*devicereg.Entity{}
+
+	for _, m := range inserted {
+		res = append(res, fromDeviceEntity(m))
+	}
+
+	return res, nil
+}
+
 func (r *deviceRepository) InsertEntityStates(ctx context.Context, states []*devicereg.EntityState) ([]*devicereg.EntityState, error) {
 	q := sq.
 		Insert("entity_states").Columns("value", "entity_id")
@@ -4,6 +4,7 @@ import (
 	"context"
 	"encoding/json"
 	"fmt"
+	"time"
 
 	"github.com/JacobSoderblom/krypin/internal/errors"
 
@@ -61,6 +62,162 @@ func (r *deviceRepository) SelectLatestStateForEntities(ctx context.Context, IDs
 	return res, nil
 }
 
+func (r *deviceRepository) SelectEntityStateHistory(ctx context.Context, entityID string, query devicereg.HistoryQuery, fn func(*devicereg.EntityState) error) error {
+	q := sq.Select("es.value", "es.created_at", "es.entity_id").
+		From("entity_states es").
+		Where(squirrel.Eq{
+			"es.entity_id": entityID,
+		})
+
+	if query.Descending {
+		q = q.OrderBy("es.created_at DESC")
+	} else {
+		q = q.OrderBy("es.created_at ASC")
+	}
+
+	if query.Since != nil {
+		q = q.Where(squirrel.GtOrEq{
+			"es.created_at": *query.Since,
+		})
+	}
+
+	if query.Until != nil {
+		q = q.Where(squirrel.Lt{
+			"es.created_at": *query.Until,
+		})
+	}
+
+	if query.Limit > 0 {
+		q = q.Limit(query.Limit)
+	}
+
+	sql, args, err := q.ToSql()
+	if err != nil {
+		return database.GetError(fmt.Errorf("could not create sql for selecting entity state history: %w", err))
+	}
+
+	tx := r.db.Tx(ctx)
+
+	rows, err := tx.Query(ctx, sql, args...)
+	if err != nil {
+		return database.GetError(fmt.Errorf("failed to select entity state history: %w", err))
+	}
+	defer rows.Close()
+
+	for rows.Next() {
+		var m entityState
+
+		if err := rows.Scan(&m.Value, &m.CreatedAt, &m.EntityID); err != nil {
+			return database.GetError(fmt.Errorf("could not scan entity state: %w", err))
+		}
+
+		if err := fn(fromEntityStateModel(&m)); err != nil {
+			return err
+		}
+	}
+
+	if err := rows.Err(); err != nil {
+		return database.GetError(fmt.Errorf("failed to read entity state history: %w", err))
+	}
+
+	return nil
+}
+
+func (r *deviceRepository) SelectStatesAt(ctx context.Context, at time.Time, entityType string) ([]*devicereg.EntityState, error) {
+	q := sq.Select("DISTINCT ON (es.entity_id) es.value", "es.created_at", "es.entity_id").
+		From("entity_states es").
+		Where(squirrel.LtOrEq{
+			"es.created_at": at,
+		}).
+		OrderBy("es.entity_id", "es.created_at DESC")
+
+	if entityType != "" {
+		q = q.Join("device_entities de ON de.id = es.entity_id").
+			Where(squirrel.Eq{
+				"de.type": entityType,
+			})
+	}
+
+	sql, args, err := q.ToSql()
+	if err != nil {
+		return nil, database.GetError(fmt.Errorf("could not create sql for selecting states at time: %w", err))
+	}
+
+	tx := r.db.Tx(ctx)
+
+	rows, err := tx.Query(ctx, sql, args...)
+	if err != nil {
+		return nil, database.GetError(fmt.Errorf("failed to select states at time: %w", err))
+	}
+	defer rows.Close()
+
+	res := []*devicereg.EntityState{}
+
+	for rows.Next() {
+		var m entityState
+
+		if err := rows.Scan(&m.Value, &m.CreatedAt, &m.EntityID); err != nil {
+			return nil, database.GetError(fmt.Errorf("could not scan entity state: %w", err))
+		}
+
+		res = append(res, fromEntityStateModel(&m))
+	}
+
+	if err := rows.Err(); err != nil {
+		return nil, database.GetError(fmt.Errorf("failed to read states at time: %w", err))
+	}
+
+	return res, nil
+}
+
+func (r *deviceRepository) SelectEntityStateStats(ctx context.Context, entityID, attribute string, since, until *time.Time) (*devicereg.EntityStateStats, error) {
+	values := sq.Select("es.created_at").
+		Column(squirrel.Expr("CASE WHEN jsonb_typeof(es.value -> ?::text) = 'number' THEN (es.value ->> ?::text)::float8 END AS v", attribute, attribute)).
+		From("entity_states es").
+		Where(squirrel.Eq{
+			"es.entity_id": entityID,
+		})
+
+	if since != nil {
+		values = values.Where(squirrel.GtOrEq{
+			"es.created_at": *since,
+		})
+	}
+
+	if until != nil {
+		values = values.Where(squirrel.Lt{
+			"es.created_at": *until,
+		})
+	}
+
+	sql, args, err := sq.
+		Select(
+			"count(v)",
+			"count(*) - count(v)",
+			"min(v)",
+			"max(v)",
+			"avg(v)",
+			"(array_agg(v ORDER BY created_at ASC) FILTER (WHERE v IS NOT NULL))[1]",
+			"(array_agg(v ORDER BY created_at DESC) FILTER (WHERE v IS NOT NULL))[1]",
+		).
+		FromSelect(values, "vals").
+		ToSql()
+	if err != nil {
+		return nil, database.GetError(fmt.Errorf("could not create sql for selecting entity state stats: %w", err))
+	}
+
+	tx := r.db.Tx(ctx)
+
+	stats := &devicereg.EntityStateStats{}
+
+	err = tx.QueryRow(ctx, sql, args...).Scan(&stats.Count, &stats.Skipped, &stats.Min, &stats.Max, &stats.Avg, &stats.First, &stats.Last)
+	if err != nil {
+		return nil, database.GetError(fmt.Errorf("failed to select entity state stats: %w", err))
+	}
+
+	return stats, nil
+}
+
 func (r *deviceRepository) selectDevices(ctx context.Context, deviceID *uuid.UUID, identifier *string) ([]*devicereg.Device, error) {
 	q, err := selectDevice(deviceID, identifier)
 	if err != nil {
@@ -0,0 +1,117 @@
+package repository_test
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"testing"
+	"time"
+
+	"github.com/JacobSoderblom/krypin/devicereg"
+	"github.com/JacobSoderblom/krypin/devicereg/repository"
+	"github.com/JacobSoderblom/krypin/internal/database"
+	"github.com/stretchr/testify/assert"
+)
+
+// errRollback rolls back the transaction a test runs in
+var errRollback = fmt.Errorf("rollback")
+
+// withTestDatabase runs fn in a transaction that is rolled back afterwards,
+// against the migrated database in KRYPIN_TEST_DATABASE_URL. The test is
+// skipped when it is not set.
+func withTestDatabase(t *testing.T, fn func(ctx context.Context, db *database.Database)) {
+	url := os.Getenv("KRYPIN_TEST_DATABASE_URL")
+	if url == "" {
+		t.Skip("KRYPIN_TEST_DATABASE_URL is not set")
+	}
+
+	os.Setenv("DATABASE_URL", url)
+
+	ctx := context.Background()
+
+	db, err := database.Connect(ctx)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer db.Close()
+
+	err = database.WithTransactionContext(ctx, db, func(ctx context.Context) error {
+		fn(ctx, db)
+		return errRollback
+	})
+	if err != errRollback {
+		t.Fatal(err)
+	}
+}
+
+func TestSelectStatesAt(t *testing.T) {
+	withTestDatabase(t, func(ctx context.Context, db *database.Database) {
+		assert := assert.New(t)
+
+		repo := repository.NewDevice(db)
+
+		d := devicereg.New("states-at-test", "Test", "Test", "1.0")
+		d.AddEntities(
+			devicereg.NewEntity("light.states_at_a", "A", "light", "test"),
+			devicereg.NewEntity("sensor.states_at_b", "B", "sensor", "test"),
+			devicereg.NewEntity("sensor.states_at_c", "C", "sensor", "test"),
+		)
+
+		_, err := repo.Insert(ctx, d)
+		assert.Nil(err)
+
+		start := time.Date(2024, 12, 1, 0, 0, 0, 0, time.UTC)
+
+		// interleaved states, c has none before start+2m
+		rows := []struct {
+			entityID string
+			minutes  int
+			value    int
+		}{
+			{"light.states_at_a", 0, 1},
+			{"sensor.states_at_b", 1, 1},
+			{"light.states_at_a", 2, 2},
+			{"sensor.states_at_c", 3, 1},
+			{"sensor.states_at_b", 4, 2},
+			{"light.states_at_a", 5, 3},
+		}
+
+		for _, r := range rows {
+			_, err := db.Tx(ctx).Exec(ctx,
+				"INSERT INTO entity_states (entity_id, value, created_at) VALUES ($1, $2, $3)",
+				r.entityID, fmt.Sprintf(`{"state": %d}`, r.value), start.Add(time.Duration(r.minutes)*time.Minute),
+			)
+			assert.Nil(err)
+		}
+
+		values := func(at time.Time, entityType string) map[string]interface{} {
+			states, err := repo.SelectStatesAt(ctx, at, entityType)
+			assert.Nil(err)
+
+			res := map[string]interface{}{}
+			for _, s := range states {
+				res[s.EntityID] = s.Value.(map[string]interface{})["state"]
+			}
+
+			return res
+		}
+
+		assert.Equal(map[string]interface{}{
+			"light.states_at_a":  float64(2),
+			"sensor.states_at_b": float64(1),
+		}, values(start.Add(2*time.Minute), ""))
+
+		assert.Equal(map[string]interface{}{
+			"light.states_at_a":  float64(2),
+			"sensor.states_at_b": float64(2),
+			"sensor.states_at_c": float64(1),
+		}, values(start.Add(4*time.Minute+30*time.Second), ""))
+
+		assert.Equal(map[string]interface{}{
+			"sensor.states_at_b": float64(2),
+			"sensor.states_at_c": float64(1),
+		}, values(start.Add(10*time.Minute), "sensor"))
+
+		assert.Empty(values(start.Add(-time.Minute), ""))
+	})
+}
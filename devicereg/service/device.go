@@ -3,6 +3,7 @@ package service
 import (
 	"context"
 	"fmt"
+	"time"
 
 	"github.com/JacobSoderblom/krypin/internal/errors"
 
@@ -10,14 +11,28 @@ import (
 	"github.com/gofrs/uuid"
 )
 
-// Repository interface
-type Repository interface {
+// DeviceRepository stores devices and their entities
+type DeviceRepository interface {
 	Insert(ctx context.Context, device *devicereg.Device) (*devicereg.Device, error)
-	InsertEntityStates(ctx context.Context, states []*devicereg.EntityState) ([]*devicereg.EntityState, error)
+	InsertEntities(ctx context.Context, deviceID uuid.UUID, entities []*devicereg.Entity) ([]*devicereg.Entity, error)
 	Select(ctx context.Context, deviceID uuid.UUID) (*devicereg.Device, error)
 	SelectAll(ctx context.Context) ([]*devicereg.Device, error)
 	SelectByIdentifier(ctx context.Context, identifier string) (*devicereg.Device, error)
+}
+
+// StateRepository stores entity states
+type StateRepository interface {
+	InsertEntityStates(ctx context.Context, states []*devicereg.EntityState) ([]*devicereg.EntityState, error)
 	SelectLatestStateForEntities(ctx context.Context, IDs ...string) ([]*devicereg.EntityState, error)
+	SelectEntityStateHistory(ctx context.Context, entityID string, q devicereg.HistoryQuery, fn func(*devicereg.EntityState) error) error
+	SelectStatesAt(ctx context.Context, at time.Time, entityType string) ([]*devicereg.EntityState, error)
+	SelectEntityStateStats(ctx context.Context, entityID, attribute string, since, until *time.Time) (*devicereg.EntityStateStats, error)
+}
+
+// Repository interface
+type Repository interface {
+	DeviceRepository
+	StateRepository
 }
 
 // NewDeviceService creates a new service for devices
@@ -31,6 +46,9 @@ type deviceService struct {
 	db Repository
 }
 
+// Add stores a new device with its entities. When the device already exists
+// only the entities it does not have yet are added, a conflict is returned if
+// there are none.
 func (s deviceService) Add(ctx context.Context, d devicereg.Device) (*devicereg.Device, error) {
 	device, err := s.db.SelectByIdentifier(ctx, d.Identifier)
 	if err != nil && !errors.Is(errors.NotFound, err) {
@@ -38,7 +56,16 @@ func (s deviceService) Add(ctx context.Context, d devicereg.Device) (*devicereg.
 	}
 
 	if device != nil {
-		return nil, errors.New(errors.Conflict, fmt.Sprintf("device with identifier (%s) already exist", d.Identifier))
+		missing := missingEntities(device, d.Entities)
+		if len(missing) == 0 {
+			return nil, errors.New(errors.Conflict, fmt.Sprintf("device with identifier (%s) already exist", d.Identifier))
+		}
+
+		if _, err := s.db.InsertEntities(ctx, device.ID, missing); err != nil {
+			return nil, err
+		}
+
+		return s.db.Select(ctx, device.ID)
 	}
 
 	device, err = s.db.Insert(ctx, &d)
@@ -60,3 +87,21 @@ func (s deviceService) List(ctx context.Context) ([]*devicereg.Device, error) {
 	}
 	return devices, nil
 }
+
+func missingEntities(device *devicereg.Device, entities []*devicereg.Entity) []*devicereg.Entity {
+	existing := map[string]bool{}
+
+	for _, e := range device.Entities {
+		existing[e.ID] = true
+	}
+
+	missing := []*devicereg.Entity{}
+
+	for _, e := range entities {
+		if !existing[e.ID] {
+			missing = append(missing, e)
+		}
+	}
+
+	return missing
+}
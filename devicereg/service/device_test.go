@@ -0,0 +1,63 @@
+package service_test
+
+import (
+	"context"
+	"testing"
+
+	"github.com/JacobSoderblom/krypin/devicereg"
+	"github.com/JacobSoderblom/krypin/devicereg/service"
+	"github.com/JacobSoderblom/krypin/internal/errors"
+	"github.com/gofrs/uuid"
+	"github.com/stretchr/testify/assert"
+)
+
+type fakeRepository struct {
+	service.Repository
+
+	device   *devicereg.Device
+	inserted []*devicereg.Entity
+}
+
+func (r *fakeRepository) SelectByIdentifier(ctx context.Context, identifier string) (*devicereg.Device, error) {
+	if r.device == nil || r.device.Identifier != identifier {
+		return nil, errors.New(errors.NotFound, "not found")
+	}
+
+	return r.device, nil
+}
+
+func (r *fakeRepository) Select(ctx context.Context, deviceID uuid.UUID) (*devicereg.Device, error) {
+	return r.device, nil
+}
+
+func (r *fakeRepository) InsertEntities(ctx context.Context, deviceID uuid.UUID, entities []*devicereg.Entity) ([]*devicereg.Entity, error) {
+	r.inserted = append(r.inserted, entities...)
+	r.device.AddEntities(entities...)
+
+	return entities, nil
+}
+
+func TestAddExistingDeviceAddsMissingEntities(t *testing.T) {
+	assert := assert.New(t)
+
+	existing := devicereg.New("shelly-1", "Shelly", "Shelly Dimmer", "1.0")
+	existing.AddEntities(devicereg.NewEntity("light.shelly_1_light_0", "Light", "light", "shelly"))
+
+	repo := &fakeRepository{device: existing}
+	svc := service.NewDeviceService(repo)
+
+	announced := devicereg.New("shelly-1", "Shelly", "Shelly Dimmer", "1.0")
+	announced.AddEntities(
+		devicereg.NewEntity("light.shelly_1_light_0", "Light", "light", "shelly"),
+		devicereg.NewEntity("sensor.shelly_1_energy", "Energy", "sensor", "shelly"),
+	)
+
+	d, err := svc.Add(context.Background(), *announced)
+	assert.Nil(err)
+	assert.Len(d.Entities, 2)
+	assert.Len(repo.inserted, 1)
+	assert.Equal("sensor.shelly_1_energy", repo.inserted[0].ID)
+
+	_, err = svc.Add(context.Background(), *announced)
+	assert.True(errors.Is(errors.Conflict, err))
+}
@@ -3,6 +3,7 @@ package service
 import (
 	"context"
 	"fmt"
+	"time"
 
 	"github.com/JacobSoderblom/krypin/devicereg"
 	"github.com/JacobSoderblom/krypin/internal/errors"
@@ -50,7 +51,11 @@ func (s deviceService) AddEntityStates(ctx context.Context, states ...*devicereg
 	res, err := s.db.InsertEntityStates(ctx, validForAdd)
 	if err != nil {
 		if errors.Is(errors.NotFound, err) {
-			return nil, errors.New(errors.Invalid, "cannot add states to entities that does not exist")
+			return nil, &errors.Error{
+				Code:    errors.Invalid,
+				Message: "cannot add states to entities that does not exist",
+				Err:     err,
+			}
 		}
 
 		return nil, err
@@ -58,3 +63,23 @@ func (s deviceService) AddEntityStates(ctx context.Context, states ...*devicereg
 
 	return res, nil
 }
+
+// StatesAt returns the state every entity had at the given time. Entities
+// without a state at or before at are omitted. An empty entityType selects
+// entities of all types.
+func (s deviceService) StatesAt(ctx context.Context, at time.Time, entityType string) ([]*devicereg.EntityState, error) {
+	return s.db.SelectStatesAt(ctx, at, entityType)
+}
+
+// EntityStateHistory calls fn for every state of the entity selected by q.
+// States are streamed from the database rather than loaded into memory.
+func (s deviceService) EntityStateHistory(ctx context.Context, entityID string, q devicereg.HistoryQuery, fn func(*devicereg.EntityState) error) error {
+	return s.db.SelectEntityStateHistory(ctx, entityID, q, fn)
+}
+
+// EntityStateStats computes count, min, max, avg, first and last of the numeric
+// value stored under attribute in the entity's states within since and until.
+// States where attribute is missing or not a number are counted as skipped.
+func (s deviceService) EntityStateStats(ctx context.Context, entityID, attribute string, since, until *time.Time) (*devicereg.EntityStateStats, error) {
+	return s.db.SelectEntityStateStats(ctx, entityID, attribute, since, until)
+}
@@ -2,6 +2,7 @@ package devicereg
 
 import (
 	"context"
+	"time"
 
 	"github.com/gofrs/uuid"
 )
@@ -11,4 +12,7 @@ type Service interface {
 	Get(ctx context.Context, id uuid.UUID) (*Device, error)
 	AddEntityStates(ctx context.Context, states ...*EntityState) ([]*EntityState, error)
 	List(ctx context.Context) ([]*Device, error)
+	EntityStateHistory(ctx context.Context, entityID string, q HistoryQuery, fn func(*EntityState) error) error
+	StatesAt(ctx context.Context, at time.Time, entityType string) ([]*EntityState, error)
+	EntityStateStats(ctx context.Context, entityID, attribute string, since, until *time.Time) (*EntityStateStats, error)
 }
@@ -1,7 +1,12 @@
 package transport
 
 import (
+	"encoding/csv"
+	"encoding/json"
+	"fmt"
 	"net/http"
+	"strconv"
+	"time"
 
 	"github.com/JacobSoderblom/krypin/internal/errors"
 
@@ -11,12 +16,20 @@ import (
 )
 
 type EndpointSet struct {
-	GetDevice echo.HandlerFunc
+	GetDevice                echo.HandlerFunc
+	GetEntityStateHistory    echo.HandlerFunc
+	ExportEntityStateHistory echo.HandlerFunc
+	GetEntityStateStats      echo.HandlerFunc
+	GetStatesAt              echo.HandlerFunc
 }
 
 func Endpoints(svc devicereg.Service) EndpointSet {
 	return EndpointSet{
-		GetDevice: GetDevice(svc),
+		GetDevice:                GetDevice(svc),
+		GetEntityStateHistory:    GetEntityStateHistory(svc),
+		ExportEntityStateHistory: ExportEntityStateHistory(svc),
+		GetEntityStateStats:      GetEntityStateStats(svc),
+		GetStatesAt:              GetStatesAt(svc),
 	}
 }
 
@@ -48,3 +61,242 @@ func GetDevice(svc devicereg.Service) echo.HandlerFunc {
 		})
 	}
 }
+
+// GetStatesAt returns the latest state of every entity at or before the
+// required RFC3339 timestamp ts. The optional domain filters by entity type,
+// e.g. light or sensor.
+func GetStatesAt(svc devicereg.Service) echo.HandlerFunc {
+	return func(c echo.Context) error {
+		at, err := parseTimeParam(c.QueryParam("ts"))
+		if err != nil || at == nil {
+			return c.NoContent(http.StatusBadRequest)
+		}
+
+		states, err := svc.StatesAt(c.Request().Context(), *at, c.QueryParam("domain"))
+		if err != nil {
+			return c.JSON(http.StatusInternalServerError, err)
+		}
+
+		return c.JSON(200, map[string]interface{}{
+			"data": states,
+		})
+	}
+}
+
+// Limits for the number of states returned by the history endpoint
+const (
+	defaultHistoryLimit = 100
+	maxHistoryLimit     = 1000
+)
+
+// GetEntityStateHistory returns states of an entity. since and until are
+// optional RFC3339 timestamps, order is asc or desc (default, newest first)
+// and limit defaults to 100 with a max of 1000. States have no unique key to
+// resume from, so use the export to read a complete range.
+func GetEntityStateHistory(svc devicereg.Service) echo.HandlerFunc {
+	return func(c echo.Context) error {
+		q := devicereg.HistoryQuery{
+			Descending: true,
+			Limit:      defaultHistoryLimit,
+		}
+
+		var err error
+
+		if q.Since, err = parseTimeParam(c.QueryParam("since")); err != nil {
+			return c.NoContent(http.StatusBadRequest)
+		}
+
+		if q.Until, err = parseTimeParam(c.QueryParam("until")); err != nil {
+			return c.NoContent(http.StatusBadRequest)
+		}
+
+		switch c.QueryParam("order") {
+		case "", "desc":
+		case "asc":
+			q.Descending = false
+		default:
+			return c.NoContent(http.StatusBadRequest)
+		}
+
+		if val := c.QueryParam("limit"); val != "" {
+			limit, err := strconv.ParseUint(val, 10, 64)
+			if err != nil || limit == 0 || limit > maxHistoryLimit {
+				return c.NoContent(http.StatusBadRequest)
+			}
+
+			q.Limit = limit
+		}
+
+		states := []*devicereg.EntityState{}
+
+		err = svc.EntityStateHistory(c.Request().Context(), c.Param("entity_id"), q, func(s *devicereg.EntityState) error {
+			states = append(states, s)
+			return nil
+		})
+		if err != nil {
+			return c.JSON(http.StatusInternalServerError, err)
+		}
+
+		return c.JSON(200, map[string]interface{}{
+			"data": states,
+		})
+	}
+}
+
+// historyFlushSize is the number of rows written between flushes of an export
+const historyFlushSize = 1000
+
+// ExportEntityStateHistory streams the state history of an entity as csv or
+// jsonl (json lines), oldest first, with timestamps in RFC3339 with
+// nanoseconds. since and until are optional RFC3339 timestamps. The status is
+// sent before streaming, so an error while streaming is logged and the
+// connection is broken off, letting the client see the export as incomplete.
+func ExportEntityStateHistory(svc devicereg.Service) echo.HandlerFunc {
+	return func(c echo.Context) error {
+		entityID := c.Param("entity_id")
+
+		format := c.QueryParam("format")
+		if format == "" {
+			format = "csv"
+		}
+
+		var contentType string
+
+		switch format {
+		case "csv":
+			contentType = "text/csv"
+		case "jsonl":
+			contentType = "application/x-ndjson"
+		default:
+			return c.NoContent(http.StatusBadRequest)
+		}
+
+		since, err := parseTimeParam(c.QueryParam("since"))
+		if err != nil {
+			return c.NoContent(http.StatusBadRequest)
+		}
+
+		until, err := parseTimeParam(c.QueryParam("until"))
+		if err != nil {
+			return c.NoContent(http.StatusBadRequest)
+		}
+
+		res := c.Response()
+		res.Header().Set(echo.HeaderContentType, contentType)
+		res.Header().Set("Content-Disposition", fmt.Sprintf("attachment; filename=%q", fmt.Sprintf("%s-history.%s", entityID, format)))
+		res.WriteHeader(http.StatusOK)
+
+		abort := func(err error) error {
+			c.Logger().Errorf("export of entity state history for %s aborted: %v", entityID, err)
+			panic(http.ErrAbortHandler)
+		}
+
+		var write func(ts string, value interface{}) error
+
+		csvWriter := csv.NewWriter(res)
+		jsonEncoder := json.NewEncoder(res)
+
+		switch format {
+		case "csv":
+			if err := csvWriter.Write([]string{"ts", "value"}); err != nil {
+				return abort(err)
+			}
+
+			write = func(ts string, value interface{}) error {
+				b, err := json.Marshal(value)
+				if err != nil {
+					return err
+				}
+
+				return csvWriter.Write([]string{ts, string(b)})
+			}
+		case "jsonl":
+			write = func(ts string, value interface{}) error {
+				return jsonEncoder.Encode(map[string]interface{}{
+					"ts":    ts,
+					"value": value,
+				})
+			}
+		}
+
+		rows := 0
+
+		q := devicereg.HistoryQuery{
+			Since: since,
+			Until: until,
+		}
+
+		err = svc.EntityStateHistory(c.Request().Context(), entityID, q, func(s *devicereg.EntityState) error {
+			if err := write(s.CreatedAt.Time.Format(time.RFC3339Nano), s.Value); err != nil {
+				return err
+			}
+
+			rows++
+			if rows%historyFlushSize == 0 {
+				csvWriter.Flush()
+				res.Flush()
+			}
+
+			return nil
+		})
+		if err != nil {
+			return abort(err)
+		}
+
+		csvWriter.Flush()
+		res.Flush()
+
+		if err := csvWriter.Error(); err != nil {
+			return abort(err)
+		}
+
+		return nil
+	}
+}
+
+// defaultStatsAttribute is the state key sensors store their value under
+const defaultStatsAttribute = "state"
+
+// GetEntityStateStats returns count, min, max, avg, first and last of a numeric
+// value in the entity's states. The value is read from the key given by the
+// attribute query param, defaulting to the sensor value.
+func GetEntityStateStats(svc devicereg.Service) echo.HandlerFunc {
+	return func(c echo.Context) error {
+		attribute := c.QueryParam("attribute")
+		if attribute == "" {
+			attribute = defaultStatsAttribute
+		}
+
+		since, err := parseTimeParam(c.QueryParam("since"))
+		if err != nil {
+			return c.NoContent(http.StatusBadRequest)
+		}
+
+		until, err := parseTimeParam(c.QueryParam("until"))
+		if err != nil {
+			return c.NoContent(http.StatusBadRequest)
+		}
+
+		stats, err := svc.EntityStateStats(c.Request().Context(), c.Param("entity_id"), attribute, since, until)
+		if err != nil {
+			return c.JSON(http.StatusInternalServerError, err)
+		}
+
+		return c.JSON(200, map[string]interface{}{
+			"data": stats,
+		})
+	}
+}
+
+func parseTimeParam(val string) (*time.Time, error) {
+	if val == "" {
+		return nil, nil
+	}
+
+	t, err := time.Parse(time.RFC3339, val)
+	if err != nil {
+		return nil, err
+	}
+
+	return &t, nil
+}
@@ -0,0 +1,240 @@
+package transport_test
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/JacobSoderblom/krypin/devicereg"
+	"github.com/JacobSoderblom/krypin/devicereg/transport"
+	"github.com/JacobSoderblom/krypin/pkg/timestamp"
+	"github.com/labstack/echo/v4"
+	"github.com/stretchr/testify/assert"
+)
+
+type historyService struct {
+	devicereg.Service
+
+	states int
+	err    error
+
+	called bool
+	query  devicereg.HistoryQuery
+}
+
+func (s *historyService) EntityStateHistory(ctx context.Context, entityID string, q devicereg.HistoryQuery, fn func(*devicereg.EntityState) error) error {
+	s.called = true
+	s.query = q
+
+	start := time.Date(2024, 12, 1, 0, 0, 0, 0, time.UTC)
+
+	for i := 0; i < s.states; i++ {
+		err := fn(&devicereg.EntityState{
+			EntityID:  entityID,
+			CreatedAt: &timestamp.Timestamp{Time: start.Add(time.Duration(i) * time.Millisecond)},
+			Value:     map[string]interface{}{"state": i},
+		})
+		if err != nil {
+			return err
+		}
+	}
+
+	return s.err
+}
+
+func serve(handler echo.HandlerFunc, query string) *httptest.ResponseRecorder {
+	e := echo.New()
+	req := httptest.NewRequest(http.MethodGet, "/?"+query, nil)
+	rec := httptest.NewRecorder()
+
+	c := e.NewContext(req, rec)
+	c.SetParamNames("entity_id")
+	c.SetParamValues("sensor.temperature")
+
+	if err := handler(c); err != nil {
+		e.HTTPErrorHandler(err, c)
+	}
+
+	return rec
+}
+
+func export(svc devicereg.Service, query string) *httptest.ResponseRecorder {
+	return serve(transport.ExportEntityStateHistory(svc), query)
+}
+
+func TestExportEntityStateHistoryStreamsCSV(t *testing.T) {
+	assert := assert.New(t)
+
+	rec := export(&historyService{states: 2500}, "format=csv")
+
+	assert.Equal(http.StatusOK, rec.Code)
+	assert.Equal("text/csv", rec.Header().Get(echo.HeaderContentType))
+
+	lines := strings.Split(strings.TrimSpace(rec.Body.String()), "\n")
+	assert.Len(lines, 2501)
+	assert.Equal("ts,value", lines[0])
+	assert.Equal(`2024-12-01T00:00:00.001Z,"{""state"":1}"`, lines[2])
+}
+
+func TestExportEntityStateHistoryAbortsOnError(t *testing.T) {
+	assert := assert.New(t)
+
+	e := echo.New()
+	rec := httptest.NewRecorder()
+
+	c := e.NewContext(httptest.NewRequest(http.MethodGet, "/?format=jsonl", nil), rec)
+	c.SetParamNames("entity_id")
+	c.SetParamValues("sensor.temperature")
+
+	handler := transport.ExportEntityStateHistory(&historyService{states: 2, err: fmt.Errorf("connection reset")})
+
+	// the panic makes net/http break off the connection instead of ending the
+	// response as if it was complete
+	assert.PanicsWithValue(http.ErrAbortHandler, func() {
+		handler(c)
+	})
+
+	lines := strings.Split(strings.TrimSpace(rec.Body.String()), "\n")
+	assert.Len(lines, 2)
+	assert.NotContains(rec.Body.String(), "connection reset")
+}
+
+type statsService struct {
+	devicereg.Service
+
+	entityID    string
+	attribute   string
+	since       *time.Time
+	until       *time.Time
+	calledStats bool
+}
+
+func (s *statsService) EntityStateStats(ctx context.Context, entityID, attribute string, since, until *time.Time) (*devicereg.EntityStateStats, error) {
+	s.calledStats = true
+	s.entityID = entityID
+	s.attribute = attribute
+	s.since = since
+	s.until = until
+
+	min, max := 18.5, 24.0
+
+	return &devicereg.EntityStateStats{Count: 3, Skipped: 1, Min: &min, Max: &max}, nil
+}
+
+func TestGetEntityStateStatsDefaultsAttribute(t *testing.T) {
+	assert := assert.New(t)
+
+	svc := &statsService{}
+	rec := serve(transport.GetEntityStateStats(svc), "")
+
+	assert.Equal(http.StatusOK, rec.Code)
+	assert.Equal("sensor.temperature", svc.entityID)
+	assert.Equal("state", svc.attribute)
+	assert.Nil(svc.since)
+	assert.Nil(svc.until)
+	assert.JSONEq(`{"data":{"count":3,"skipped":1,"min":18.5,"max":24,"avg":null,"first":null,"last":null}}`, rec.Body.String())
+}
+
+func TestGetEntityStateStatsPassesParams(t *testing.T) {
+	assert := assert.New(t)
+
+	svc := &statsService{}
+	rec := serve(transport.GetEntityStateStats(svc), "attribute=humidity&since=2024-12-01T00:00:00Z&until=2024-12-02T00:00:00Z")
+
+	assert.Equal(http.StatusOK, rec.Code)
+	assert.Equal("humidity", svc.attribute)
+	assert.Equal(time.Date(2024, 12, 1, 0, 0, 0, 0, time.UTC), svc.since.UTC())
+	assert.Equal(time.Date(2024, 12, 2, 0, 0, 0, 0, time.UTC), svc.until.UTC())
+}
+
+func TestGetEntityStateStatsRejectsInvalidTimes(t *testing.T) {
+	assert := assert.New(t)
+
+	for _, query := range []string{"since=yesterday", "until=2024-12-01"} {
+		svc := &statsService{}
+		rec := serve(transport.GetEntityStateStats(svc), query)
+
+		assert.Equal(http.StatusBadRequest, rec.Code, query)
+		assert.False(svc.calledStats, query)
+	}
+}
+
+type statesAtService struct {
+	devicereg.Service
+
+	at         time.Time
+	entityType string
+	called     bool
+}
+
+func (s *statesAtService) StatesAt(ctx context.Context, at time.Time, entityType string) ([]*devicereg.EntityState, error) {
+	s.called = true
+	s.at = at
+	s.entityType = entityType
+
+	return []*devicereg.EntityState{}, nil
+}
+
+func TestGetStatesAtPassesParams(t *testing.T) {
+	assert := assert.New(t)
+
+	svc := &statesAtService{}
+	rec := serve(transport.GetStatesAt(svc), "ts=2024-12-01T00:00:00Z&domain=light")
+
+	assert.Equal(http.StatusOK, rec.Code)
+	assert.Equal(time.Date(2024, 12, 1, 0, 0, 0, 0, time.UTC), svc.at.UTC())
+	assert.Equal("light", svc.entityType)
+	assert.JSONEq(`{"data":[]}`, rec.Body.String())
+}
+
+func TestGetStatesAtRequiresTimestamp(t *testing.T) {
+	assert := assert.New(t)
+
+	for _, query := range []string{"", "ts=2024-12-01"} {
+		svc := &statesAtService{}
+		rec := serve(transport.GetStatesAt(svc), query)
+
+		assert.Equal(http.StatusBadRequest, rec.Code, query)
+		assert.False(svc.called, query)
+	}
+}
+
+func TestGetEntityStateHistoryDefaults(t *testing.T) {
+	assert := assert.New(t)
+
+	svc := &historyService{states: 3}
+	rec := serve(transport.GetEntityStateHistory(svc), "")
+
+	assert.Equal(http.StatusOK, rec.Code)
+	assert.Equal(devicereg.HistoryQuery{Descending: true, Limit: 100}, svc.query)
+	assert.Contains(rec.Body.String(), `"data":[`)
+}
+
+func TestGetEntityStateHistoryParams(t *testing.T) {
+	assert := assert.New(t)
+
+	svc := &historyService{}
+	rec := serve(transport.GetEntityStateHistory(svc), "order=asc&limit=1000&since=2024-12-01T00:00:00Z")
+
+	assert.Equal(http.StatusOK, rec.Code)
+	assert.False(svc.query.Descending)
+	assert.Equal(uint64(1000), svc.query.Limit)
+	assert.Equal(time.Date(2024, 12, 1, 0, 0, 0, 0, time.UTC), svc.query.Since.UTC())
+	assert.Nil(svc.query.Until)
+}
+
+func TestGetEntityStateHistoryRejectsInvalidParams(t *testing.T) {
+	assert := assert.New(t)
+
+	for _, query := range []string{"limit=0", "limit=1001", "limit=ten", "order=newest", "until=tomorrow", "since=2024-12-01"} {
+		svc := &historyService{}
+		rec := serve(transport.GetEntityStateHistory(svc), query)
+
+		assert.Equal(http.StatusBadRequest, rec.Code, query)
+		assert.False(svc.called, query)
+	}
+}
@@ -4,6 +4,8 @@ import (
 	"context"
 	"encoding/json"
 	"fmt"
+	"sync"
+	"time"
 
 	"github.com/JacobSoderblom/krypin/internal/errors"
 	"github.com/JacobSoderblom/krypin/pkg/websocket"
@@ -30,12 +32,12 @@ func Discover(svc devicereg.Service, socket websocket.Broadcaster) pubsub.Handle
 		d := devicereg.Device{}
 		err := ev.GetPayload(&d)
 		if err != nil {
-			return errors.New(errors.Internal, fmt.Sprintf("could not parse event payload as device: %s", err.Error()))
+			return errors.New(errors.Invalid, fmt.Sprintf("could not parse event payload as device: %s", err.Error()))
 		}
 
 		device, err := svc.Add(ctx, d)
 		if errors.Is(errors.Conflict, err) {
-			// do nothing if we already have that device
+			// do nothing if we already have that device with all its entities
 			return nil
 		}
 
@@ -55,16 +57,41 @@ func Discover(svc devicereg.Service, socket websocket.Broadcaster) pubsub.Handle
 	}
 }
 
+// DiscoveryRequestCooldown is the shortest time between two discovery requests for the same entity
+const DiscoveryRequestCooldown = 5 * time.Minute
+
+// EntityStateUpdate stores the states in the event. When states arrive for
+// entities the hub does not know, a discovery request is published for each
+// of them, at most once per DiscoveryRequestCooldown, so the owning module can
+// announce its device again.
 func EntityStateUpdate(svc devicereg.Service) pubsub.Handler {
+	var m sync.Mutex
+	requested := map[string]time.Time{}
+
 	return func(ctx context.Context, ev *event.Event, publisher pubsub.Publish) error {
 		states := []*devicereg.EntityState{}
 
 		err := ev.GetPayload(&states)
 		if err != nil {
-			return errors.New(errors.Internal, fmt.Sprintf("could not parse event payload as entities: %s", err.Error()))
+			return errors.New(errors.Invalid, fmt.Sprintf("could not parse event payload as entities: %s", err.Error()))
 		}
 
 		_, err = svc.AddEntityStates(ctx, states...)
+		if errors.Is(errors.NotFound, err) {
+			m.Lock()
+			defer m.Unlock()
+
+			for _, s := range states {
+				if last, found := requested[s.EntityID]; found && time.Since(last) < DiscoveryRequestCooldown {
+					continue
+				}
+
+				requested[s.EntityID] = time.Now()
+
+				publisher(event.NewDiscoveryRequest(s.EntityID))
+			}
+		}
+
 		if err != nil {
 			return err
 		}
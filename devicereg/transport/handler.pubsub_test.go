@@ -0,0 +1,88 @@
+package transport_test
+
+import (
+	"context"
+	"testing"
+
+	"github.com/JacobSoderblom/krypin/devicereg"
+	"github.com/JacobSoderblom/krypin/devicereg/transport"
+	"github.com/JacobSoderblom/krypin/internal/errors"
+	"github.com/JacobSoderblom/krypin/internal/event"
+	"github.com/stretchr/testify/assert"
+)
+
+type fakeService struct {
+	devicereg.Service
+
+	entities map[string]bool
+}
+
+func (s *fakeService) Add(ctx context.Context, d devicereg.Device) (*devicereg.Device, error) {
+	for _, e := range d.Entities {
+		s.entities[e.ID] = true
+	}
+
+	return &d, nil
+}
+
+func (s *fakeService) AddEntityStates(ctx context.Context, states ...*devicereg.EntityState) ([]*devicereg.EntityState, error) {
+	for _, state := range states {
+		if !s.entities[state.EntityID] {
+			return nil, &errors.Error{
+				Code: errors.Invalid,
+				Err:  errors.New(errors.NotFound, "entity does not exist"),
+			}
+		}
+	}
+
+	return states, nil
+}
+
+type fakeBroadcaster struct {
+	messages [][]byte
+}
+
+func (b *fakeBroadcaster) Broadcast(msg []byte) error {
+	b.messages = append(b.messages, msg)
+	return nil
+}
+
+func TestUnknownEntityIsRediscovered(t *testing.T) {
+	assert := assert.New(t)
+
+	svc := &fakeService{entities: map[string]bool{}}
+	socket := &fakeBroadcaster{}
+	handlers := transport.PubSubHandlers(svc, socket)
+
+	published := []*event.Event{}
+	publish := func(ev *event.Event) error {
+		published = append(published, ev)
+		return nil
+	}
+
+	update := event.NewEntityStateUpdate([]*devicereg.EntityState{
+		{EntityID: "binary_switch.shelly_1_switch_0", Value: map[string]interface{}{"is_on": true}},
+	})
+	update = event.Parse(update.Bytes(), update.Topic)
+
+	err := handlers.EntityStateUpdate(context.Background(), update, publish)
+	assert.True(errors.Is(errors.NotFound, err))
+	assert.Len(published, 1)
+	assert.Equal("krypin/discovery/request/binary_switch.shelly_1_switch_0", published[0].Topic)
+
+	// a second update within the cooldown does not request discovery again
+	handlers.EntityStateUpdate(context.Background(), update, publish)
+	assert.Len(published, 1)
+
+	d := devicereg.New("shelly-1", "Shelly", "Shelly Plus Plug S", "1.0.3")
+	d.AddEntities(devicereg.NewEntity("binary_switch.shelly_1_switch_0", "Switch", "binary_switch", "shelly"))
+
+	announce := event.NewDiscovered(d)
+	announce = event.Parse(announce.Bytes(), announce.Topic)
+
+	assert.Nil(handlers.Discover(context.Background(), announce, publish))
+	assert.Len(socket.messages, 1)
+
+	assert.Nil(handlers.EntityStateUpdate(context.Background(), update, publish))
+	assert.Len(published, 1)
+}
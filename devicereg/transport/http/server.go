@@ -7,6 +7,15 @@ import (
 )
 
 func NewHttpHandler(endpoints transport.EndpointSet, g *echo.Group, db *database.Database) *echo.Group {
+	states := g.Group("/states")
+
+	states.Use(database.HttpTransaction(db))
+
+	states.GET("/at", endpoints.GetStatesAt)
+	states.GET("/:entity_id/history", endpoints.GetEntityStateHistory)
+	states.GET("/:entity_id/history/export", endpoints.ExportEntityStateHistory)
+	states.GET("/:entity_id/stats", endpoints.GetEntityStateStats)
+
 	g = g.Group("/devices")
 
 	g.Use(database.HttpTransaction(db))
@@ -2,7 +2,9 @@ package pubsub_transport
 
 import (
 	"context"
+	"encoding/json"
 
+	"github.com/JacobSoderblom/krypin/internal/errors"
 	"github.com/JacobSoderblom/krypin/pkg/log"
 
 	"github.com/JacobSoderblom/krypin/devicereg/transport"
@@ -13,25 +15,63 @@ import (
 	"github.com/JacobSoderblom/krypin/pkg/protocol/mqtt"
 )
 
-func NewPubSubActor(handlers transport.PubSubHandlerSet, client *mqtt.Client, db *database.Database, errLogger log.Logger) (func(context.Context) error, func(error)) {
-	act := pubsub.New(client)
+func NewPubSubActor(handlers transport.PubSubHandlerSet, client *mqtt.Client, db *database.Database, errLogger log.Logger, options ...pubsub.Option) (func(context.Context) error, func(error)) {
+	act := pubsub.New(client, errLogger, options...)
 
-	act.Handle(event.DiscoveredTopic, handlers.Discover, ErrorLogger(errLogger), database.PubSubTransaction(db))
-	act.Handle(event.EntityStateTopic, handlers.EntityStateUpdate, ErrorLogger(errLogger), database.PubSubTransaction(db))
+	invalidLogger := log.NewRateLimited(errLogger, pubsub.InvalidPayloadLogInterval)
+
+	act.Handle(event.DiscoveredTopic, handlers.Discover, ErrorLogger(errLogger, invalidLogger), database.PubSubTransaction(db))
+	act.Handle(event.EntityStateTopic, handlers.EntityStateUpdate, ErrorLogger(errLogger, invalidLogger), database.PubSubTransaction(db))
 
 	return act.Execute, act.Interrupt
 }
 
-func ErrorLogger(errLogger log.Logger) pubsub.Middleware {
+// ErrorLogger logs errors returned by the handler. Invalid payload errors are
+// logged through invalidLogger, keyed by topic and the device or entity the
+// payload is from when that can be read, so a misbehaving device can not flood
+// the log or hide the errors of other devices. When too many sources are seen
+// the key falls back to the topic. States for unknown entities are also
+// invalid but are logged in full, they would otherwise share the bucket with
+// malformed payloads and hide them.
+func ErrorLogger(errLogger log.Logger, invalidLogger *log.RateLimited) pubsub.Middleware {
 	return func(next pubsub.Handler) pubsub.Handler {
 		return func(ctx context.Context, ev *event.Event, publisher pubsub.Publish) (err error) {
 			defer func() {
-				if err != nil {
-					errLogger.Log("error", err)
+				if err == nil {
+					return
+				}
+
+				if errors.Is(errors.Invalid, err) && !errors.Is(errors.NotFound, err) {
+					invalidLogger.LogWithFallback(ev.Topic+"/"+source(ev), ev.Topic, "error", err, "topic", ev.Topic)
+					return
 				}
+
+				errLogger.Log("error", err)
 			}()
 
 			return next(ctx, ev, publisher)
 		}
 	}
 }
+
+// source returns the device identifier or first entity id in the payload, or
+// an empty string if the payload has neither
+func source(ev *event.Event) string {
+	var device struct {
+		Identifier string `json:"identifier"`
+	}
+
+	if err := json.Unmarshal(ev.Payload, &device); err == nil && device.Identifier != "" {
+		return device.Identifier
+	}
+
+	var states []struct {
+		EntityID string `json:"entity_id"`
+	}
+
+	if err := json.Unmarshal(ev.Payload, &states); err == nil && len(states) > 0 {
+		return states[0].EntityID
+	}
+
+	return ""
+}
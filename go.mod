@@ -6,7 +6,7 @@ require (
 	github.com/Masterminds/squirrel v1.4.0
 	github.com/eclipse/paho.mqtt.golang v1.2.0
 	github.com/gofrs/uuid v3.2.0+incompatible
-	github.com/gorilla/websocket v1.4.2 // indirect
+	github.com/gorilla/websocket v1.4.2
 	github.com/imdario/mergo v0.3.11
 	github.com/jackc/pgconn v1.6.4
 	github.com/jackc/pgerrcode v0.0.0-20190803225404-afa3381909a6
@@ -0,0 +1,124 @@
+package bridge
+
+import (
+	"context"
+	"fmt"
+	"sync/atomic"
+	"time"
+
+	"github.com/JacobSoderblom/krypin/pkg/log"
+	"github.com/JacobSoderblom/krypin/pkg/protocol/mqtt"
+)
+
+// Bridge mirrors messages matching a set of topic filters from the primary
+// broker to a secondary broker. It only publishes to the secondary broker and
+// never subscribes to it, so messages can not flow back to the primary.
+//
+// Topics are subscribed at QoS 2 so messages keep the QoS they were published
+// with. Brokers only set the retain flag on messages delivered when
+// subscribing, so retained messages published while the bridge is running
+// are mirrored without it.
+//
+// Messages are queued for the secondary broker without blocking the primary
+// client, so a slow or reconnecting secondary can not stall the hub. When the
+// queue is full messages are dropped and counted.
+type Bridge struct {
+	// dropped is kept first for 64-bit alignment on 32-bit platforms
+	dropped uint64
+
+	primary        *mqtt.Client
+	secondary      *mqtt.Client
+	topics         map[string]byte
+	msgs           chan mqtt.Message
+	errLogger      log.Logger
+	overflowLogger *log.RateLimited
+}
+
+// QueueSize is the number of messages waiting for the secondary broker before
+// new messages are dropped
+const QueueSize = 1000
+
+// PublishTimeout is how long to wait for the secondary broker to accept a
+// message
+const PublishTimeout = 5 * time.Second
+
+// OverflowLogInterval is how often dropped messages are logged
+const OverflowLogInterval = time.Minute
+
+// New creates a bridge mirroring topics from primary to secondary
+func New(primary, secondary *mqtt.Client, topics []string, errLogger log.Logger) *Bridge {
+	filter := map[string]byte{}
+
+	for _, t := range topics {
+		filter[t] = 2
+	}
+
+	return &Bridge{
+		primary:   primary,
+		secondary: secondary,
+		topics:    filter,
+		msgs:           make(chan mqtt.Message, QueueSize),
+		errLogger:      errLogger,
+		overflowLogger: log.NewRateLimited(errLogger, OverflowLogInterval),
+	}
+}
+
+// Dropped returns the number of messages dropped because the queue was full
+func (b *Bridge) Dropped() uint64 {
+	return atomic.LoadUint64(&b.dropped)
+}
+
+func (b *Bridge) Execute(ctx context.Context) error {
+	// the primary uses clean sessions, so subscriptions are lost when it
+	// reconnects and have to be made again
+	b.primary.AddConnectionHandler(func(c *mqtt.Client, _ *mqtt.Message) {
+		if ctx.Err() == nil {
+			b.subscribe()
+		}
+	})
+
+	b.subscribe()
+
+	for {
+		select {
+		case msg := <-b.msgs:
+			t := b.secondary.Publish(msg.Topic(), msg.Qos(), msg.Retained(), msg.Payload())
+			if !t.WaitTimeout(PublishTimeout) {
+				b.errLogger.Log("error", fmt.Errorf("timed out mirroring message on %s", msg.Topic()))
+				continue
+			}
+
+			if t.Error() != nil {
+				b.errLogger.Log("error", fmt.Errorf("could not mirror message on %s: %w", msg.Topic(), t.Error()))
+			}
+		case <-ctx.Done():
+			topics := []string{}
+			for t := range b.topics {
+				topics = append(topics, t)
+			}
+
+			if t := b.primary.Unsubscribe(topics...); t.WaitTimeout(PublishTimeout) && t.Error() != nil {
+				b.errLogger.Log("error", fmt.Errorf("could not unsubscribe bridged topics: %w", t.Error()))
+			}
+
+			b.secondary.Disconnect(250)
+
+			return ctx.Err()
+		}
+	}
+}
+
+func (b *Bridge) Interrupt(err error) {}
+
+func (b *Bridge) subscribe() {
+	b.primary.SubscribeMultipleFn(b.topics, b.enqueue)
+}
+
+func (b *Bridge) enqueue(msg mqtt.Message) {
+	select {
+	case b.msgs <- msg:
+	default:
+		dropped := atomic.AddUint64(&b.dropped, 1)
+		b.overflowLogger.Log("overflow", "error", "bridge queue is full, dropping message", "topic", msg.Topic(), "dropped", dropped)
+	}
+}
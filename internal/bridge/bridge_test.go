@@ -0,0 +1,41 @@
+package bridge
+
+import (
+	"testing"
+
+	"github.com/JacobSoderblom/krypin/pkg/protocol/mqtt"
+	MQTT "github.com/eclipse/paho.mqtt.golang"
+	"github.com/stretchr/testify/assert"
+)
+
+type fakeMessage struct {
+	MQTT.Message
+}
+
+func (m fakeMessage) Topic() string {
+	return "krypin/entity/state_update"
+}
+
+type recordingLogger struct {
+	entries [][]interface{}
+}
+
+func (l *recordingLogger) Log(keyvals ...interface{}) error {
+	l.entries = append(l.entries, keyvals)
+	return nil
+}
+
+func TestEnqueueDropsWhenQueueIsFull(t *testing.T) {
+	assert := assert.New(t)
+
+	logger := &recordingLogger{}
+	b := New(nil, nil, []string{"krypin/#"}, logger)
+
+	for i := 0; i < QueueSize+5; i++ {
+		b.enqueue(mqtt.Message{Message: fakeMessage{}})
+	}
+
+	assert.Len(b.msgs, QueueSize)
+	assert.Equal(uint64(5), b.Dropped())
+	assert.Len(logger.entries, 1)
+}
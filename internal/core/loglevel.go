@@ -0,0 +1,45 @@
+package core
+
+import (
+	"net/http"
+
+	"github.com/labstack/echo/v4"
+	"github.com/sirupsen/logrus"
+)
+
+type logLevel struct {
+	Level string `json:"level"`
+}
+
+// GetLogLevel returns a handler responding with the current process log level
+func GetLogLevel() echo.HandlerFunc {
+	return func(c echo.Context) error {
+		return c.JSON(http.StatusOK, map[string]interface{}{
+			"data": logLevel{Level: logrus.GetLevel().String()},
+		})
+	}
+}
+
+// SetLogLevel returns a handler changing the process log level at runtime.
+// The change is not persisted and is lost on restart.
+func SetLogLevel() echo.HandlerFunc {
+	return func(c echo.Context) error {
+		var req logLevel
+		if err := c.Bind(&req); err != nil {
+			return c.NoContent(http.StatusBadRequest)
+		}
+
+		level, err := logrus.ParseLevel(req.Level)
+		if err != nil {
+			return c.JSON(http.StatusBadRequest, map[string]interface{}{
+				"error": err.Error(),
+			})
+		}
+
+		logrus.SetLevel(level)
+
+		return c.JSON(http.StatusOK, map[string]interface{}{
+			"data": logLevel{Level: level.String()},
+		})
+	}
+}
@@ -13,6 +13,7 @@ import (
 const timeFormat = "2006-01-02 15:04:05.000"
 const DiscoveredTopic = "device/discovered"
 const EntityStateTopic = "entity/state_update"
+const DiscoveryRequestTopic = "discovery/request/+"
 
 type Event struct {
 	Topic     string              `json:"-"`
@@ -79,3 +80,10 @@ func NewEntityStateUpdate(e []*devicereg.EntityState) *Event {
 
 	return ev
 }
+
+// NewDiscoveryRequest creates an event asking the module owning entityID to announce its device again
+func NewDiscoveryRequest(entityID string) *Event {
+	ev := New(fmt.Sprintf("discovery/request/%s", entityID))
+
+	return ev
+}
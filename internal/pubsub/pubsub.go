@@ -5,9 +5,11 @@ import (
 	"fmt"
 	"regexp"
 	"strings"
+	"time"
 
 	"github.com/JacobSoderblom/krypin/internal/event"
 	"github.com/JacobSoderblom/krypin/pkg/actor/mqttact"
+	"github.com/JacobSoderblom/krypin/pkg/log"
 	"github.com/JacobSoderblom/krypin/pkg/protocol/mqtt"
 )
 
@@ -19,18 +21,44 @@ type Publish func(*event.Event) error
 type Handler func(context.Context, *event.Event, Publish) error
 type Middleware func(next Handler) Handler
 
-func New(client *mqtt.Client) *PubSub {
-	return &PubSub{
-		mqtt:     client,
-		act:      mqttact.New(client),
-		handlers: map[string]Handler{},
+// InvalidPayloadLogInterval is how often malformed payloads are logged per topic
+const InvalidPayloadLogInterval = time.Minute
+
+// DefaultMaxPayloadSize is the largest message payload, in bytes, handled by default
+const DefaultMaxPayloadSize = 256 * 1024
+
+type Option func(*PubSub)
+
+// WithMaxPayloadSize sets the largest message payload, in bytes, that is
+// handled. Larger messages are dropped before being parsed.
+func WithMaxPayloadSize(size int) Option {
+	return func(p *PubSub) {
+		p.maxPayloadSize = size
+	}
+}
+
+func New(client *mqtt.Client, errLogger log.Logger, options ...Option) *PubSub {
+	p := &PubSub{
+		mqtt:           client,
+		act:            mqttact.New(client),
+		handlers:       map[string]Handler{},
+		invalidLogger:  log.NewRateLimited(errLogger, InvalidPayloadLogInterval),
+		maxPayloadSize: DefaultMaxPayloadSize,
 	}
+
+	for _, optFunc := range options {
+		optFunc(p)
+	}
+
+	return p
 }
 
 type PubSub struct {
-	handlers map[string]Handler
-	mqtt     *mqtt.Client
-	act      *mqttact.Actor
+	handlers       map[string]Handler
+	mqtt           *mqtt.Client
+	act            *mqttact.Actor
+	invalidLogger  *log.RateLimited
+	maxPayloadSize int
 }
 
 func (p *PubSub) Handle(topic string, fn Handler, middlwares ...Middleware) {
@@ -44,13 +72,22 @@ func (p *PubSub) Handle(topic string, fn Handler, middlwares ...Middleware) {
 }
 
 func (p *PubSub) Publish(ev *event.Event) error {
-	t := p.mqtt.Publish(ev.Topic, 0, false, ev)
+	t := p.mqtt.Publish(ev.Topic, 0, false, ev.Bytes())
 
 	return t.Error()
 }
 
 func (p *PubSub) handleMqttMessage(ctx context.Context, msg mqtt.Message, publisher mqtt.Publish) error {
+	if size := len(msg.Payload()); size > p.maxPayloadSize {
+		p.invalidLogger.Log(msg.Topic(), "error", "message payload too large", "topic", msg.Topic(), "size", size, "max_size", p.maxPayloadSize)
+		return nil
+	}
+
 	ev := event.Parse(msg.Payload(), msg.Topic())
+	if ev == nil {
+		p.invalidLogger.Log(msg.Topic(), "error", "could not parse message as event", "topic", msg.Topic())
+		return nil
+	}
 
 	for topic, handler := range p.handlers {
 		foundOneLevel, _ := regexp.MatchString(strings.ReplaceAll(topic, "+", "([a-zA-Z0-9 _.-]+)"), msg.Topic())
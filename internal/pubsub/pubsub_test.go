@@ -0,0 +1,64 @@
+package pubsub
+
+import (
+	"context"
+	"strings"
+	"testing"
+
+	"github.com/JacobSoderblom/krypin/internal/event"
+	"github.com/JacobSoderblom/krypin/pkg/protocol/mqtt"
+	MQTT "github.com/eclipse/paho.mqtt.golang"
+	"github.com/stretchr/testify/assert"
+)
+
+type fakeMessage struct {
+	MQTT.Message
+
+	topic   string
+	payload []byte
+}
+
+func (m fakeMessage) Topic() string {
+	return m.topic
+}
+
+func (m fakeMessage) Payload() []byte {
+	return m.payload
+}
+
+type recordingLogger struct {
+	entries [][]interface{}
+}
+
+func (l *recordingLogger) Log(keyvals ...interface{}) error {
+	l.entries = append(l.entries, keyvals)
+	return nil
+}
+
+func TestHandleMqttMessageDropsOversizedPayload(t *testing.T) {
+	assert := assert.New(t)
+
+	logger := &recordingLogger{}
+	p := New(nil, logger, WithMaxPayloadSize(64))
+
+	calls := 0
+	p.Handle(event.EntityStateTopic, func(ctx context.Context, ev *event.Event, publish Publish) error {
+		calls++
+		return nil
+	})
+
+	ev := event.New(event.EntityStateTopic)
+	ev.SetPayload(strings.Repeat("a", 128))
+
+	msg := mqtt.Message{Message: fakeMessage{topic: ev.Topic, payload: ev.Bytes()}}
+
+	assert.Nil(p.handleMqttMessage(context.Background(), msg, nil))
+	assert.Equal(0, calls)
+	assert.Len(logger.entries, 1)
+
+	ev.SetPayload("a")
+	msg = mqtt.Message{Message: fakeMessage{topic: ev.Topic, payload: ev.Bytes()}}
+
+	assert.Nil(p.handleMqttMessage(context.Background(), msg, nil))
+	assert.Equal(1, calls)
+}
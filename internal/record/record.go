@@ -0,0 +1,141 @@
+package record
+
+import (
+	"bufio"
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"sync/atomic"
+	"time"
+
+	"github.com/JacobSoderblom/krypin/pkg/log"
+	"github.com/JacobSoderblom/krypin/pkg/protocol/mqtt"
+)
+
+// Topic is the topic filter recorded by the Recorder
+const Topic = "krypin/#"
+
+// Entry is a recorded bus message. A recording is a file of json lines, one
+// entry per line: {"topic": "...", "payload": "<base64>", "ts": "<RFC3339>"}.
+type Entry struct {
+	Topic     string    `json:"topic"`
+	Payload   []byte    `json:"payload"`
+	Timestamp time.Time `json:"ts"`
+}
+
+// QueueSize is the number of messages waiting to be written before new
+// messages are dropped
+const QueueSize = 1000
+
+// OverflowLogInterval is how often dropped messages are logged
+const OverflowLogInterval = time.Minute
+
+// Recorder appends every krypin bus message to a file as json lines. Messages
+// are queued without blocking the MQTT client, when the queue is full they
+// are dropped and counted.
+type Recorder struct {
+	// dropped is kept first for 64-bit alignment on 32-bit platforms
+	dropped uint64
+
+	client         *mqtt.Client
+	path           string
+	msgs           chan mqtt.Message
+	errLogger      log.Logger
+	overflowLogger *log.RateLimited
+}
+
+// NewRecorder creates a recorder writing to the file at path
+func NewRecorder(client *mqtt.Client, path string, errLogger log.Logger) *Recorder {
+	return &Recorder{
+		client:         client,
+		path:           path,
+		msgs:           make(chan mqtt.Message, QueueSize),
+		errLogger:      errLogger,
+		overflowLogger: log.NewRateLimited(errLogger, OverflowLogInterval),
+	}
+}
+
+// Dropped returns the number of messages dropped because the queue was full
+func (r *Recorder) Dropped() uint64 {
+	return atomic.LoadUint64(&r.dropped)
+}
+
+func (r *Recorder) Execute(ctx context.Context) error {
+	f, err := os.OpenFile(r.path, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0644)
+	if err != nil {
+		return fmt.Errorf("could not open bus recording file: %w", err)
+	}
+	defer f.Close()
+
+	// the client uses clean sessions, so the subscription is lost when it
+	// reconnects and has to be made again
+	r.client.AddConnectionHandler(func(c *mqtt.Client, _ *mqtt.Message) {
+		if ctx.Err() == nil {
+			r.subscribe()
+		}
+	})
+
+	r.subscribe()
+
+	for {
+		select {
+		case msg := <-r.msgs:
+			err := Write(f, Entry{
+				Topic:     msg.Topic(),
+				Payload:   msg.Payload(),
+				Timestamp: msg.ReceivedAt,
+			})
+			if err != nil {
+				r.errLogger.Log("error", fmt.Errorf("could not record bus message: %w", err))
+			}
+		case <-ctx.Done():
+			if t := r.client.Unsubscribe(Topic); t.WaitTimeout(5*time.Second) && t.Error() != nil {
+				r.errLogger.Log("error", fmt.Errorf("could not unsubscribe recorded topic: %w", t.Error()))
+			}
+
+			return ctx.Err()
+		}
+	}
+}
+
+func (r *Recorder) Interrupt(err error) {}
+
+func (r *Recorder) subscribe() {
+	r.client.SubscribeFn(Topic, 0, r.enqueue)
+}
+
+func (r *Recorder) enqueue(msg mqtt.Message) {
+	select {
+	case r.msgs <- msg:
+	default:
+		dropped := atomic.AddUint64(&r.dropped, 1)
+		r.overflowLogger.Log("overflow", "error", "recorder queue is full, dropping message", "topic", msg.Topic(), "dropped", dropped)
+	}
+}
+
+// Write appends an entry to a recording
+func Write(w io.Writer, e Entry) error {
+	return json.NewEncoder(w).Encode(e)
+}
+
+// Read calls fn for every entry in a recording, in the order they were recorded
+func Read(rd io.Reader, fn func(Entry) error) error {
+	scanner := bufio.NewScanner(rd)
+	scanner.Buffer(make([]byte, 64*1024), 16*1024*1024)
+
+	for scanner.Scan() {
+		var e Entry
+
+		if err := json.Unmarshal(scanner.Bytes(), &e); err != nil {
+			return fmt.Errorf("could not parse recorded entry: %w", err)
+		}
+
+		if err := fn(e); err != nil {
+			return err
+		}
+	}
+
+	return scanner.Err()
+}
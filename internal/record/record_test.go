@@ -0,0 +1,37 @@
+package record_test
+
+import (
+	"bytes"
+	"testing"
+	"time"
+
+	"github.com/JacobSoderblom/krypin/internal/record"
+	"github.com/stretchr/testify/assert"
+)
+
+func TestRecordingRoundTrip(t *testing.T) {
+	assert := assert.New(t)
+
+	ts := time.Date(2020, 10, 1, 12, 0, 0, 0, time.UTC)
+
+	entries := []record.Entry{
+		{Topic: "krypin/device/discovered", Payload: []byte(`{"timestamp":1601553600}`), Timestamp: ts},
+		{Topic: "krypin/entity/state_update", Payload: []byte{0x00, 0xff, '\n'}, Timestamp: ts.Add(1500 * time.Millisecond)},
+	}
+
+	var buf bytes.Buffer
+
+	for _, e := range entries {
+		assert.Nil(record.Write(&buf, e))
+	}
+
+	read := []record.Entry{}
+
+	err := record.Read(&buf, func(e record.Entry) error {
+		read = append(read, e)
+		return nil
+	})
+
+	assert.Nil(err)
+	assert.Equal(entries, read)
+}
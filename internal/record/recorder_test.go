@@ -0,0 +1,36 @@
+package record
+
+import (
+	"testing"
+
+	"github.com/JacobSoderblom/krypin/pkg/protocol/mqtt"
+	MQTT "github.com/eclipse/paho.mqtt.golang"
+	"github.com/stretchr/testify/assert"
+)
+
+type fakeMessage struct {
+	MQTT.Message
+}
+
+func (m fakeMessage) Topic() string {
+	return "krypin/entity/state_update"
+}
+
+type nopLogger struct{}
+
+func (nopLogger) Log(keyvals ...interface{}) error {
+	return nil
+}
+
+func TestEnqueueDropsWhenQueueIsFull(t *testing.T) {
+	assert := assert.New(t)
+
+	r := NewRecorder(nil, "", nopLogger{})
+
+	for i := 0; i < QueueSize+3; i++ {
+		r.enqueue(mqtt.Message{Message: fakeMessage{}})
+	}
+
+	assert.Len(r.msgs, QueueSize)
+	assert.Equal(uint64(3), r.Dropped())
+}
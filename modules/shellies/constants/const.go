@@ -45,6 +45,18 @@ var (
 	ShellyDuoName = "Shellybulbduo"
 )
 
+// constants for Shelly Gen2 devices, models are matched by prefix since the
+// suffix differs between regional variants e.g. SNPL-00112EU and SNPL-00116US
+var (
+	ShellyPlusPlugSID    = "SNPL-00112EU"
+	ShellyPlusPlugSName  = "Shelly Plus Plug S"
+	ShellyPlusPlugPrefix = "SNPL-"
+	ShellyPlusPlugName   = "Shelly Plus Plug"
+
+	ShellyPlus1PMPrefix = "SNSW-001P"
+	ShellyPlus1PMName   = "Shelly Plus 1PM"
+)
+
 func RemovePrefix(id string) string {
 	return id[strings.Index(id, "-")+1:]
 }
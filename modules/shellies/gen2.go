@@ -0,0 +1,142 @@
+package main
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"strings"
+	"sync"
+
+	"github.com/JacobSoderblom/krypin/devicereg"
+	"github.com/JacobSoderblom/krypin/internal/event"
+	"github.com/JacobSoderblom/krypin/modules/shellies/parse"
+	"github.com/JacobSoderblom/krypin/modules/shellies/rpc"
+	"github.com/JacobSoderblom/krypin/pkg/log"
+	"github.com/JacobSoderblom/krypin/pkg/protocol/mqtt"
+)
+
+// gen2Poller polls Shelly Gen2 devices over their local HTTP RPC interface
+// and publishes discovery and state update events for them
+type gen2Poller struct {
+	clients   []*rpc.Client
+	publisher mqtt.Publish
+	errLogger log.Logger
+
+	// devices and last are only used by Poll, m guards reannounce so that
+	// Reannounce never waits for a poll in progress
+	m          sync.Mutex
+	reannounce bool
+	devices    map[string]*rpc.DeviceInfo
+	last       map[string][]byte
+}
+
+func newGen2Poller(hosts string, publisher mqtt.Publish, errLogger log.Logger) *gen2Poller {
+	p := &gen2Poller{
+		publisher: publisher,
+		errLogger: errLogger,
+		devices:   map[string]*rpc.DeviceInfo{},
+		last:      map[string][]byte{},
+	}
+
+	for _, host := range strings.Split(hosts, ",") {
+		host = strings.TrimSpace(host)
+		if host == "" {
+			continue
+		}
+
+		p.clients = append(p.clients, rpc.New(host))
+	}
+
+	return p
+}
+
+func (p *gen2Poller) Poll(ctx context.Context) {
+	p.m.Lock()
+	// the current state is published again as well, since the hub rejects
+	// states for entities it did not know about
+	if p.reannounce {
+		p.devices = map[string]*rpc.DeviceInfo{}
+		p.last = map[string][]byte{}
+		p.reannounce = false
+	}
+	p.m.Unlock()
+
+	for _, c := range p.clients {
+		if err := p.pollDevice(ctx, c); err != nil {
+			p.errLogger.Log("error", err, "host", c.Host())
+		}
+	}
+}
+
+// Reannounce makes the next poll announce all devices and publish their
+// current state again
+func (p *gen2Poller) Reannounce() {
+	p.m.Lock()
+	defer p.m.Unlock()
+
+	p.reannounce = true
+}
+
+func (p *gen2Poller) pollDevice(ctx context.Context, c *rpc.Client) error {
+	info, found := p.devices[c.Host()]
+	if !found {
+		var err error
+
+		info, err = c.GetDeviceInfo(ctx)
+		if err != nil {
+			return err
+		}
+
+		// announcing an unsupported model would register the device without
+		// entities, and existing devices are never announced again
+		if parse.Gen2SwitchCount(info.Model) == 0 {
+			p.errLogger.Log("error", "unsupported gen2 model, skipping announce", "host", c.Host(), "model", info.Model)
+			p.devices[c.Host()] = info
+
+			return nil
+		}
+
+		ev := event.NewDiscovered(parse.ParseGen2DeviceInfo(c.Host(), info))
+
+		if err := p.publisher(ev.Topic, 0, false, ev.Bytes()).Error(); err != nil {
+			return err
+		}
+
+		p.devices[c.Host()] = info
+	}
+
+	states := []*devicereg.EntityState{}
+
+	for i := 0; i < parse.Gen2SwitchCount(info.Model); i++ {
+		status, err := c.GetSwitchStatus(ctx, i)
+		if err != nil {
+			return err
+		}
+
+		states = append(states, parse.ParseGen2SwitchStatus(info.ID, status)...)
+	}
+
+	if len(states) == 0 {
+		return nil
+	}
+
+	// only publish when something changed since the last poll
+	b, err := json.Marshal(states)
+	if err != nil {
+		return err
+	}
+
+	if bytes.Equal(b, p.last[c.Host()]) {
+		return nil
+	}
+
+	ev := event.NewEntityStateUpdate(states)
+
+	if err := p.publisher(ev.Topic, 0, false, ev.Bytes()).Error(); err != nil {
+		return err
+	}
+
+	p.last[c.Host()] = b
+
+	return nil
+}
@@ -0,0 +1,82 @@
+package main
+
+import (
+	"context"
+	"io/ioutil"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+
+	MQTT "github.com/eclipse/paho.mqtt.golang"
+	"github.com/stretchr/testify/assert"
+)
+
+type doneToken struct {
+	MQTT.Token
+}
+
+func (t doneToken) Wait() bool {
+	return true
+}
+
+func (t doneToken) WaitTimeout(time.Duration) bool {
+	return true
+}
+
+func (t doneToken) Error() error {
+	return nil
+}
+
+type nopLogger struct{}
+
+func (nopLogger) Log(keyvals ...interface{}) error {
+	return nil
+}
+
+func TestReannouncePublishesCurrentState(t *testing.T) {
+	assert := assert.New(t)
+
+	deviceInfo, err := ioutil.ReadFile("testdata/plus_plug_s_device_info.json")
+	assert.Nil(err)
+
+	switchStatus, err := ioutil.ReadFile("testdata/plus_plug_s_switch_status.json")
+	assert.Nil(err)
+
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		switch r.URL.Path {
+		case "/rpc/Shelly.GetDeviceInfo":
+			w.Write(deviceInfo)
+		case "/rpc/Switch.GetStatus":
+			w.Write(switchStatus)
+		default:
+			http.NotFound(w, r)
+		}
+	}))
+	defer server.Close()
+
+	topics := []string{}
+	publish := func(topic string, qos byte, retained bool, payload interface{}) MQTT.Token {
+		topics = append(topics, topic)
+		return doneToken{}
+	}
+
+	p := newGen2Poller(strings.TrimPrefix(server.URL, "http://"), publish, nopLogger{})
+
+	p.Poll(context.Background())
+	assert.Equal([]string{"krypin/device/discovered", "krypin/entity/state_update"}, topics)
+
+	// unchanged state is not published again
+	p.Poll(context.Background())
+	assert.Len(topics, 2)
+
+	p.Reannounce()
+	p.Poll(context.Background())
+	assert.Equal([]string{
+		"krypin/device/discovered",
+		"krypin/entity/state_update",
+		"krypin/device/discovered",
+		"krypin/entity/state_update",
+	}, topics)
+}
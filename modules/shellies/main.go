@@ -12,6 +12,7 @@ import (
 	"github.com/JacobSoderblom/krypin/modules/shellies/parse"
 	"github.com/JacobSoderblom/krypin/pkg/actor/mqttact"
 	"github.com/JacobSoderblom/krypin/pkg/actor/ticker"
+	"github.com/JacobSoderblom/krypin/pkg/log"
 	"github.com/JacobSoderblom/krypin/pkg/protocol/mqtt"
 )
 
@@ -22,6 +23,7 @@ func main() {
 
 	logger := core.NewLogger("shelly")
 	errLogger := core.NewErrorLogger("shelly")
+	invalidLogger := log.NewRateLimited(errLogger, time.Minute)
 
 	mqttClient := core.OpenMqtt()
 
@@ -31,7 +33,7 @@ func main() {
 
 		d, err := parse.ParseAnnounce(msg.Payload())
 		if err != nil {
-			errLogger.Log("error", err)
+			invalidLogger.Log(msg.Topic(), "error", err, "topic", msg.Topic())
 			return err
 		}
 
@@ -48,7 +50,7 @@ func main() {
 
 		e, err := parse.ParseInfo(identifier, msg.Payload())
 		if err != nil {
-			errLogger.Log("error", err)
+			invalidLogger.Log(msg.Topic(), "error", err, "topic", msg.Topic())
 			return err
 		}
 
@@ -57,6 +59,26 @@ func main() {
 		return publisher(ev.Topic, 0, false, ev.Bytes()).Error()
 	})
 
+	var gen2 *gen2Poller
+
+	if hosts := os.Getenv("SHELLY_GEN2_HOSTS"); hosts != "" {
+		gen2 = newGen2Poller(hosts, mqttClient.Publish, errLogger)
+	}
+
+	// the hub asks for a new announce when it receives states for entities it does not know
+	mqttAct.Handle(fmt.Sprintf("krypin/%s", event.DiscoveryRequestTopic), func(ctx context.Context, msg mqtt.Message, publisher mqtt.Publish) error {
+		entityID := msg.Topic()[strings.LastIndex(msg.Topic(), "/")+1:]
+		if !strings.Contains(entityID, "shelly") {
+			return nil
+		}
+
+		if gen2 != nil {
+			gen2.Reannounce()
+		}
+
+		return publisher("shellies/command", 0, false, "announce").Error()
+	})
+
 	tickAct := ticker.New(60*time.Second, func(ctx context.Context) {
 		mqttClient.Publish("shellies/command", 0, false, "announce")
 	})
@@ -66,6 +88,12 @@ func main() {
 	m.Add(mqttAct.Execute, mqttAct.Interrupt)
 	m.Add(tickAct.Execute, tickAct.Interrupt)
 
+	if gen2 != nil {
+		gen2Act := ticker.New(10*time.Second, gen2.Poll)
+
+		m.Add(gen2Act.Execute, gen2Act.Interrupt)
+	}
+
 	go func() {
 		time.Sleep(100)
 
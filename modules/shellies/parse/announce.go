@@ -45,7 +45,7 @@ func getModelName(model string) string {
 	case constants.ShellyDimmer2ID:
 		return constants.ShellyDimmer2Name
 	default:
-		return ""
+		return gen2ModelName(model)
 	}
 }
 
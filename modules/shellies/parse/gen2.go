@@ -0,0 +1,84 @@
+package parse
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/JacobSoderblom/krypin/devicereg"
+	"github.com/JacobSoderblom/krypin/devicereg/entity"
+	"github.com/JacobSoderblom/krypin/modules/shellies/constants"
+	"github.com/JacobSoderblom/krypin/modules/shellies/rpc"
+)
+
+// Gen2SwitchCount returns the number of switch components for a Gen2 model,
+// zero means that the model is not supported
+func Gen2SwitchCount(model string) int {
+	switch {
+	case strings.HasPrefix(model, constants.ShellyPlusPlugPrefix),
+		strings.HasPrefix(model, constants.ShellyPlus1PMPrefix):
+		return 1
+	}
+
+	return 0
+}
+
+func gen2ModelName(model string) string {
+	switch {
+	case model == constants.ShellyPlusPlugSID:
+		return constants.ShellyPlusPlugSName
+	case strings.HasPrefix(model, constants.ShellyPlusPlugPrefix):
+		return constants.ShellyPlusPlugName
+	case strings.HasPrefix(model, constants.ShellyPlus1PMPrefix):
+		return constants.ShellyPlus1PMName
+	}
+
+	return ""
+}
+
+// ParseGen2DeviceInfo creates a device with its entities from the device info of a Gen2 device
+func ParseGen2DeviceInfo(host string, info *rpc.DeviceInfo) *devicereg.Device {
+	d := devicereg.New(info.ID, "Shelly", getModelName(info.Model), info.SoftwareVersion)
+
+	d.AddConnection("network_mac", info.Mac)
+	d.AddConnection("network_ip", host)
+	d.AddConnection("http", "shelly_rpc")
+
+	for i := 0; i < Gen2SwitchCount(info.Model); i++ {
+		d.AddEntities(
+			entity.NewSwitch(fmt.Sprintf("%s Switch %v", d.Identifier, i), "shelly"),
+			entity.NewSensor(fmt.Sprintf("%s Power %v", d.Identifier, i), "shelly"),
+			entity.NewSensor(fmt.Sprintf("%s Energy %v", d.Identifier, i), "shelly"),
+		)
+	}
+
+	return d
+}
+
+// ParseGen2SwitchStatus converts the status of a Gen2 switch component to entity states
+func ParseGen2SwitchStatus(identifier string, status *rpc.SwitchStatus) []*devicereg.EntityState {
+	sw := entity.NewSwitch(fmt.Sprintf("%s Switch %v", identifier, status.ID), "shelly")
+	sw.AddState(entity.Switch{
+		IsOn: status.Output,
+	})
+
+	power := entity.NewSensor(fmt.Sprintf("%s Power %v", identifier, status.ID), "shelly")
+	power.AddState(entity.Sensor{
+		UnitOfMeasurement: entity.Watt,
+		Class:             entity.Power,
+		Value:             status.APower,
+	})
+
+	energy := entity.NewSensor(fmt.Sprintf("%s Energy %v", identifier, status.ID), "shelly")
+	energy.AddState(entity.Sensor{
+		UnitOfMeasurement: entity.WattHour,
+		Class:             entity.Energy,
+		Value:             status.AEnergy.Total,
+	})
+
+	states := []*devicereg.EntityState{}
+	states = append(states, sw.States...)
+	states = append(states, power.States...)
+	states = append(states, energy.States...)
+
+	return states
+}
@@ -0,0 +1,59 @@
+package parse_test
+
+import (
+	"encoding/json"
+	"io/ioutil"
+	"testing"
+
+	"github.com/JacobSoderblom/krypin/devicereg/entity"
+	"github.com/JacobSoderblom/krypin/modules/shellies/parse"
+	"github.com/JacobSoderblom/krypin/modules/shellies/rpc"
+	"github.com/stretchr/testify/assert"
+)
+
+func TestParseGen2PlusPlugS(t *testing.T) {
+	assert := assert.New(t)
+
+	b, err := ioutil.ReadFile("../testdata/plus_plug_s_device_info.json")
+	assert.Nil(err)
+
+	info := &rpc.DeviceInfo{}
+	assert.Nil(json.Unmarshal(b, info))
+
+	d := parse.ParseGen2DeviceInfo("192.168.20.50", info)
+
+	assert.Equal("shellyplusplugs-e86beae8d4b8", d.Identifier)
+	assert.Equal("Shelly Plus Plug S", d.Model)
+	assert.Equal("1.0.3", d.SoftwareVersion)
+	assert.Len(d.Entities, 3)
+
+	b, err = ioutil.ReadFile("../testdata/plus_plug_s_switch_status.json")
+	assert.Nil(err)
+
+	status := &rpc.SwitchStatus{}
+	assert.Nil(json.Unmarshal(b, status))
+
+	states := parse.ParseGen2SwitchStatus(info.ID, status)
+
+	assert.Len(states, 3)
+
+	for i, s := range states {
+		assert.Equal(d.Entities[i].ID, s.EntityID)
+	}
+
+	assert.Equal("binary_switch.shellyplusplugs_e86beae8d4b8_switch_0", states[0].EntityID)
+	assert.Equal(entity.Switch{IsOn: true}, states[0].Value)
+	assert.Equal(float32(8.9), states[1].Value.(entity.Sensor).Value)
+	assert.Equal(float32(1234.567), states[2].Value.(entity.Sensor).Value)
+}
+
+func TestGen2SwitchCount(t *testing.T) {
+	assert := assert.New(t)
+
+	assert.Equal(1, parse.Gen2SwitchCount("SNPL-00112EU"))
+	assert.Equal(1, parse.Gen2SwitchCount("SNPL-00116US"))
+	assert.Equal(1, parse.Gen2SwitchCount("SNSW-001P16EU"))
+	assert.Equal(1, parse.Gen2SwitchCount("SNSW-001P15UL"))
+	assert.Equal(0, parse.Gen2SwitchCount("SNSW-001X16EU"))
+	assert.Equal(0, parse.Gen2SwitchCount("SNSN-0013A"))
+}
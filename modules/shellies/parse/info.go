@@ -42,11 +42,15 @@ func ParseInfo(identifier string, payload []byte) ([]*devicereg.EntityState, err
 	for i, l := range i.Lights {
 		e := entity.NewLight(fmt.Sprintf("%s Light %v", identifier, i), "shelly")
 
-		e.AddState(entity.Light{
+		state := entity.Light{
 			IsOn:       l.IsOn,
 			Brightness: l.Brightness,
 			Mode:       l.Mode,
-		})
+			ColorMode:  colorMode(l.Mode),
+		}
+		state.ColorMode = state.ActiveColorMode()
+
+		e.AddState(state)
 
 		states = append(states, e.States...)
 	}
@@ -84,3 +88,15 @@ func ParseInfo(identifier string, payload []byte) ([]*devicereg.EntityState, err
 
 	return states, nil
 }
+
+// colorMode maps the mode reported by Shelly bulbs to a light color mode
+func colorMode(mode string) string {
+	switch mode {
+	case "color":
+		return entity.ColorModeRGB
+	case "white":
+		return entity.ColorModeTemperature
+	}
+
+	return ""
+}
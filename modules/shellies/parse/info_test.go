@@ -0,0 +1,22 @@
+package parse_test
+
+import (
+	"testing"
+
+	"github.com/JacobSoderblom/krypin/devicereg/entity"
+	"github.com/JacobSoderblom/krypin/modules/shellies/parse"
+	"github.com/stretchr/testify/assert"
+)
+
+func TestParseInfoLightColorMode(t *testing.T) {
+	assert := assert.New(t)
+
+	payload := `{"lights":[{"ison":true,"mode":"color","brightness":50},{"ison":true,"mode":"white","brightness":50},{"ison":false,"brightness":10}]}`
+
+	states, err := parse.ParseInfo("shellybulb-1", []byte(payload))
+	assert.Nil(err)
+
+	assert.Equal(entity.ColorModeRGB, states[0].Value.(entity.Light).ColorMode)
+	assert.Equal(entity.ColorModeTemperature, states[1].Value.(entity.Light).ColorMode)
+	assert.Equal(entity.ColorModeWhite, states[2].Value.(entity.Light).ColorMode)
+}
@@ -0,0 +1,106 @@
+package rpc
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/url"
+	"strconv"
+	"time"
+
+	"github.com/pkg/errors"
+)
+
+// Client talks to the local HTTP RPC interface of a Shelly Gen2 device
+type Client struct {
+	host string
+	http *http.Client
+}
+
+// New creates a new client for the device at host
+func New(host string) *Client {
+	return &Client{
+		host: host,
+		http: &http.Client{Timeout: 5 * time.Second},
+	}
+}
+
+// Host returns the host of the device
+func (c *Client) Host() string {
+	return c.host
+}
+
+// DeviceInfo is the result of Shelly.GetDeviceInfo
+type DeviceInfo struct {
+	ID              string `json:"id"`
+	Mac             string `json:"mac"`
+	Model           string `json:"model"`
+	Gen             int    `json:"gen"`
+	SoftwareVersion string `json:"ver"`
+}
+
+// SwitchStatus is the result of Switch.GetStatus
+type SwitchStatus struct {
+	ID      int     `json:"id"`
+	Output  bool    `json:"output"`
+	APower  float32 `json:"apower"`
+	AEnergy energy  `json:"aenergy"`
+}
+
+type energy struct {
+	Total float32 `json:"total"`
+}
+
+// GetDeviceInfo fetches the identity of the device
+func (c *Client) GetDeviceInfo(ctx context.Context) (*DeviceInfo, error) {
+	info := &DeviceInfo{}
+
+	if err := c.call(ctx, "Shelly.GetDeviceInfo", nil, info); err != nil {
+		return nil, err
+	}
+
+	return info, nil
+}
+
+// GetSwitchStatus fetches the status of the switch component with id
+func (c *Client) GetSwitchStatus(ctx context.Context, id int) (*SwitchStatus, error) {
+	status := &SwitchStatus{}
+
+	params := url.Values{}
+	params.Set("id", strconv.Itoa(id))
+
+	if err := c.call(ctx, "Switch.GetStatus", params, status); err != nil {
+		return nil, err
+	}
+
+	return status, nil
+}
+
+func (c *Client) call(ctx context.Context, method string, params url.Values, dst interface{}) error {
+	u := fmt.Sprintf("http://%s/rpc/%s", c.host, method)
+	if len(params) > 0 {
+		u = fmt.Sprintf("%s?%s", u, params.Encode())
+	}
+
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, u, nil)
+	if err != nil {
+		return errors.Wrapf(err, "Failed to create request for %s", method)
+	}
+
+	res, err := c.http.Do(req)
+	if err != nil {
+		return errors.Wrapf(err, "Failed to call %s on %s", method, c.host)
+	}
+	defer res.Body.Close()
+
+	if res.StatusCode != http.StatusOK {
+		return fmt.Errorf("%s on %s responded with status %d", method, c.host, res.StatusCode)
+	}
+
+	if err := json.NewDecoder(res.Body).Decode(dst); err != nil {
+		return errors.Wrapf(err, "Failed to parse %s response", method)
+	}
+
+	return nil
+}
@@ -0,0 +1,77 @@
+package rpc_test
+
+import (
+	"context"
+	"io/ioutil"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/JacobSoderblom/krypin/modules/shellies/rpc"
+	"github.com/stretchr/testify/assert"
+)
+
+func newDevice(t *testing.T) (*rpc.Client, func()) {
+	deviceInfo, err := ioutil.ReadFile("../testdata/plus_plug_s_device_info.json")
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	switchStatus, err := ioutil.ReadFile("../testdata/plus_plug_s_switch_status.json")
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		switch r.URL.Path {
+		case "/rpc/Shelly.GetDeviceInfo":
+			w.Write(deviceInfo)
+		case "/rpc/Switch.GetStatus":
+			if r.URL.Query().Get("id") != "0" {
+				http.Error(w, "no such component", http.StatusBadRequest)
+				return
+			}
+
+			w.Write(switchStatus)
+		default:
+			http.NotFound(w, r)
+		}
+	}))
+
+	return rpc.New(strings.TrimPrefix(server.URL, "http://")), server.Close
+}
+
+func TestGetDeviceInfo(t *testing.T) {
+	assert := assert.New(t)
+
+	c, done := newDevice(t)
+	defer done()
+
+	info, err := c.GetDeviceInfo(context.Background())
+	assert.Nil(err)
+
+	assert.Equal("shellyplusplugs-e86beae8d4b8", info.ID)
+	assert.Equal("E86BEAE8D4B8", info.Mac)
+	assert.Equal("SNPL-00112EU", info.Model)
+	assert.Equal(2, info.Gen)
+	assert.Equal("1.0.3", info.SoftwareVersion)
+}
+
+func TestGetSwitchStatus(t *testing.T) {
+	assert := assert.New(t)
+
+	c, done := newDevice(t)
+	defer done()
+
+	status, err := c.GetSwitchStatus(context.Background(), 0)
+	assert.Nil(err)
+
+	assert.Equal(0, status.ID)
+	assert.True(status.Output)
+	assert.Equal(float32(8.9), status.APower)
+	assert.Equal(float32(1234.567), status.AEnergy.Total)
+
+	_, err = c.GetSwitchStatus(context.Background(), 1)
+	assert.NotNil(err)
+}
@@ -0,0 +1,106 @@
+package log
+
+import (
+	"sync"
+	"time"
+)
+
+// RateLimited wraps a Logger so that repeated events for the same key are
+// logged on first occurrence and then at most once per interval. The number
+// of events suppressed in between is added to the next logged event.
+//
+// Keys not seen within the interval are forgotten, and at most maxKeys are
+// tracked so that keys taken from untrusted input can not grow it without
+// bound.
+type RateLimited struct {
+	logger   Logger
+	interval time.Duration
+	maxKeys  int
+	now      func() time.Time
+
+	m         sync.Mutex
+	seen      map[string]*rateLimitEntry
+	lastSweep time.Time
+}
+
+// MaxRateLimitedKeys is the number of keys tracked by a RateLimited logger
+const MaxRateLimitedKeys = 1000
+
+type rateLimitEntry struct {
+	last       time.Time
+	suppressed int
+}
+
+// NewRateLimited creates a rate limited logger logging through logger
+func NewRateLimited(logger Logger, interval time.Duration) *RateLimited {
+	return &RateLimited{
+		logger:   logger,
+		interval: interval,
+		maxKeys:  MaxRateLimitedKeys,
+		now:      time.Now,
+		seen:     map[string]*rateLimitEntry{},
+	}
+}
+
+// Log logs keyvals unless an event with the same key was logged within the interval
+func (r *RateLimited) Log(key string, keyvals ...interface{}) error {
+	return r.log(key, "", keyvals...)
+}
+
+// LogWithFallback is like Log, but uses fallback as the key when the maximum
+// number of keys are already tracked
+func (r *RateLimited) LogWithFallback(key, fallback string, keyvals ...interface{}) error {
+	return r.log(key, fallback, keyvals...)
+}
+
+func (r *RateLimited) log(key, fallback string, keyvals ...interface{}) error {
+	r.m.Lock()
+
+	now := r.now()
+
+	r.sweep(now, key)
+
+	entry, found := r.seen[key]
+	if !found && fallback != "" && len(r.seen) >= r.maxKeys {
+		key = fallback
+		entry, found = r.seen[key]
+	}
+
+	if found && now.Sub(entry.last) < r.interval {
+		entry.suppressed++
+		r.m.Unlock()
+
+		return nil
+	}
+
+	suppressed := 0
+	if found {
+		suppressed = entry.suppressed
+	}
+
+	r.seen[key] = &rateLimitEntry{last: now}
+
+	r.m.Unlock()
+
+	if suppressed > 0 {
+		keyvals = append(keyvals[:len(keyvals):len(keyvals)], "suppressed", suppressed)
+	}
+
+	return r.logger.Log(keyvals...)
+}
+
+// sweep forgets keys not seen within the interval, at most once per interval.
+// keep is not forgotten so that its suppressed count is logged.
+func (r *RateLimited) sweep(now time.Time, keep string) {
+	if now.Sub(r.lastSweep) < r.interval {
+		return
+	}
+
+	r.lastSweep = now
+
+	for key, entry := range r.seen {
+		if key != keep && now.Sub(entry.last) >= r.interval {
+			delete(r.seen, key)
+		}
+	}
+}
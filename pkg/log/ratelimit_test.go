@@ -0,0 +1,81 @@
+package log
+
+import (
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+)
+
+type recordingLogger struct {
+	events [][]interface{}
+}
+
+func (l *recordingLogger) Log(keyvals ...interface{}) error {
+	l.events = append(l.events, keyvals)
+	return nil
+}
+
+func TestRateLimitedSuppressesRepeatedKeys(t *testing.T) {
+	assert := assert.New(t)
+
+	rec := &recordingLogger{}
+	now := time.Unix(0, 0)
+
+	l := NewRateLimited(rec, time.Minute)
+	l.now = func() time.Time { return now }
+
+	l.Log("krypin/entity/state_update", "error", "bad payload")
+	l.Log("krypin/entity/state_update", "error", "bad payload")
+	l.Log("krypin/entity/state_update", "error", "bad payload")
+	l.Log("krypin/device/discovered", "error", "bad payload")
+
+	assert.Len(rec.events, 2)
+
+	now = now.Add(time.Minute)
+
+	l.Log("krypin/entity/state_update", "error", "bad payload")
+
+	assert.Len(rec.events, 3)
+	assert.Equal([]interface{}{"error", "bad payload", "suppressed", 2}, rec.events[2])
+}
+
+func TestRateLimitedForgetsStaleKeys(t *testing.T) {
+	assert := assert.New(t)
+
+	rec := &recordingLogger{}
+	now := time.Unix(0, 0)
+
+	l := NewRateLimited(rec, time.Minute)
+	l.now = func() time.Time { return now }
+
+	l.Log("a", "error", "bad payload")
+	l.Log("b", "error", "bad payload")
+
+	now = now.Add(time.Minute)
+
+	l.Log("c", "error", "bad payload")
+
+	assert.Len(l.seen, 1)
+	assert.Len(rec.events, 3)
+}
+
+func TestRateLimitedFallsBackWhenFull(t *testing.T) {
+	assert := assert.New(t)
+
+	rec := &recordingLogger{}
+	now := time.Unix(0, 0)
+
+	l := NewRateLimited(rec, time.Minute)
+	l.now = func() time.Time { return now }
+	l.maxKeys = 2
+
+	l.LogWithFallback("topic/a", "topic", "error", "bad payload")
+	l.LogWithFallback("topic/b", "topic", "error", "bad payload")
+	l.LogWithFallback("topic/c", "topic", "error", "bad payload")
+	l.LogWithFallback("topic/d", "topic", "error", "bad payload")
+	l.LogWithFallback("topic/e", "topic", "error", "bad payload")
+
+	assert.Len(rec.events, 3)
+	assert.Len(l.seen, 3)
+}
@@ -5,6 +5,7 @@ import (
 	"math/rand"
 	"os"
 	"sync"
+	"time"
 
 	MQTT "github.com/eclipse/paho.mqtt.golang"
 	"github.com/pkg/errors"
@@ -16,8 +17,15 @@ type Handlers []Handler
 
 type Publish func(topic string, qos byte, retained bool, payload interface{}) MQTT.Token
 
+// Message is a message received from the broker. Whether it was a retained
+// message is available through Retained() on the embedded message.
 type Message struct {
 	MQTT.Message
+	ReceivedAt time.Time
+}
+
+func newMessage(msg MQTT.Message) Message {
+	return Message{Message: msg, ReceivedAt: time.Now().UTC()}
 }
 
 type ClientOptions struct {
@@ -69,7 +77,7 @@ func (c *Client) Subscribe(topic string, qos byte) <-chan Message {
 	messageChannel := make(chan Message)
 
 	c.Client.Subscribe(topic, qos, func(client MQTT.Client, msg MQTT.Message) {
-		messageChannel <- Message{Message: msg}
+		messageChannel <- newMessage(msg)
 	})
 
 	return messageChannel
@@ -77,7 +85,7 @@ func (c *Client) Subscribe(topic string, qos byte) <-chan Message {
 
 func (c *Client) SubscribeFn(topic string, qos byte, fn func(msg Message)) {
 	c.Client.Subscribe(topic, qos, func(client MQTT.Client, msg MQTT.Message) {
-		fn(Message{Message: msg})
+		fn(newMessage(msg))
 	})
 }
 
@@ -85,22 +93,26 @@ func (c *Client) SubscribeMultiple(filter map[string]byte) <-chan Message {
 	messageChannel := make(chan Message)
 
 	c.Client.SubscribeMultiple(filter, func(client MQTT.Client, msg MQTT.Message) {
-		messageChannel <- Message{Message: msg}
+		messageChannel <- newMessage(msg)
 	})
 
 	return messageChannel
 }
 
+func (c *Client) SubscribeMultipleFn(filter map[string]byte, fn func(msg Message)) {
+	c.Client.SubscribeMultiple(filter, func(client MQTT.Client, msg MQTT.Message) {
+		fn(newMessage(msg))
+	})
+}
+
 func (c *Client) defaultPublishHandler(client MQTT.Client, msg MQTT.Message) {
 	c.m.Lock()
 	defer c.m.Unlock()
 
-	m := &Message{
-		Message: msg,
-	}
+	m := newMessage(msg)
 
 	for _, h := range c.onDefaultPublishHandlers {
-		h(c, m)
+		h(c, &m)
 	}
 }
 
@@ -0,0 +1,57 @@
+package spa
+
+import (
+	"os"
+	"path"
+	"path/filepath"
+	"regexp"
+	"strings"
+
+	"github.com/labstack/echo/v4"
+)
+
+// Handler serves the single page application in dir. It must be registered
+// on a wildcard route, e.g. /ui/*. Paths that do not match a file fall back
+// to index.html so the application can handle its own routing. Hashed assets
+// are cached for a year, everything else is revalidated on every request.
+func Handler(dir string) echo.HandlerFunc {
+	index := filepath.Join(dir, "index.html")
+
+	return func(c echo.Context) error {
+		p := path.Clean("/" + c.Param("*"))
+		file := filepath.Join(dir, filepath.FromSlash(p))
+
+		if fi, err := os.Stat(file); err != nil || fi.IsDir() {
+			c.Response().Header().Set("Cache-Control", "no-cache")
+			return c.File(index)
+		}
+
+		if IsHashedAsset(path.Base(p)) {
+			c.Response().Header().Set("Cache-Control", "public, max-age=31536000, immutable")
+		} else {
+			c.Response().Header().Set("Cache-Control", "no-cache")
+		}
+
+		return c.File(file)
+	}
+}
+
+var hashSegment = regexp.MustCompile(`[.-]([0-9A-Za-z_]{8,})\.[0-9A-Za-z]+$`)
+
+// IsHashedAsset reports whether a file name contains a content hash added by
+// a bundler, e.g. main.3f2a9c1b.js or index-BfK3j2aX.css
+func IsHashedAsset(name string) bool {
+	matches := hashSegment.FindStringSubmatch(name)
+	if len(matches) < 2 {
+		return false
+	}
+
+	// require both a letter and a digit so words like "datepicker" don't count
+	hash := matches[1]
+
+	return strings.ContainsAny(hash, "0123456789") && strings.IndexFunc(hash, isLetter) >= 0
+}
+
+func isLetter(r rune) bool {
+	return (r >= 'a' && r <= 'z') || (r >= 'A' && r <= 'Z')
+}
@@ -0,0 +1,50 @@
+package spa_test
+
+import (
+	"io/ioutil"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/JacobSoderblom/krypin/pkg/spa"
+	"github.com/labstack/echo/v4"
+	"github.com/stretchr/testify/assert"
+)
+
+func TestIsHashedAsset(t *testing.T) {
+	assert := assert.New(t)
+
+	assert.True(spa.IsHashedAsset("main.3f2a9c1b.js"))
+	assert.True(spa.IsHashedAsset("index-BfK3j2aX.css"))
+	assert.False(spa.IsHashedAsset("index.html"))
+	assert.False(spa.IsHashedAsset("bootstrap-datepicker.js"))
+}
+
+func TestHandlerFallsBackToIndex(t *testing.T) {
+	assert := assert.New(t)
+
+	dir, err := ioutil.TempDir("", "spa")
+	assert.Nil(err)
+	defer os.RemoveAll(dir)
+
+	assert.Nil(ioutil.WriteFile(filepath.Join(dir, "index.html"), []byte("index"), 0644))
+	assert.Nil(ioutil.WriteFile(filepath.Join(dir, "main.3f2a9c1b.js"), []byte("main"), 0644))
+
+	e := echo.New()
+	e.GET("/ui/*", spa.Handler(dir))
+
+	for path, body := range map[string]string{
+		"/ui/":                 "index",
+		"/ui/devices/1":        "index",
+		"/ui/../../etc/passwd": "index",
+		"/ui/main.3f2a9c1b.js": "main",
+	} {
+		rec := httptest.NewRecorder()
+		e.ServeHTTP(rec, httptest.NewRequest(http.MethodGet, path, nil))
+
+		assert.Equal(http.StatusOK, rec.Code, path)
+		assert.Equal(body, rec.Body.String(), path)
+	}
+}
@@ -4,6 +4,7 @@ import (
 	"encoding/json"
 	"fmt"
 	"net/http"
+	"sync/atomic"
 
 	"gopkg.in/olahol/melody.v1"
 )
@@ -16,13 +17,17 @@ type Session struct {
 
 	msession *melody.Session
 	mrouter  *melody.Melody
+	router   *Router
+	stream   *stream
 }
 
-func newSession(msg []byte, s *melody.Session, r *melody.Melody) (*Session, error) {
+func newSession(msg []byte, s *melody.Session, r *Router, st *stream) (*Session, error) {
 	session := &Session{
 		msession: s,
-		mrouter:  r,
+		mrouter:  r.mrouter,
+		router:   r,
 		Request:  s.Request,
+		stream:   st,
 	}
 
 	var req Request
@@ -97,6 +102,10 @@ func (s *Session) Broadcast(topic string, data interface{}) error {
 		return NewError("internal", fmt.Sprintf("failed to broadcast to other sessions: %v", err))
 	}
 
+	if s.router != nil {
+		s.router.countSent(s.msession)
+	}
+
 	return nil
 }
 
@@ -118,6 +127,10 @@ func (s *Session) send(resp Response) error {
 		return NewError("internal", fmt.Sprintf("failed to write message to session: %v", err))
 	}
 
+	if s.stream != nil {
+		atomic.AddUint64(&s.stream.sent, 1)
+	}
+
 	return nil
 }
 
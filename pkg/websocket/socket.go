@@ -4,6 +4,7 @@ import (
 	"context"
 	"fmt"
 	"sync"
+	"sync/atomic"
 
 	"gopkg.in/olahol/melody.v1"
 )
@@ -20,8 +21,10 @@ type Router struct {
 	connectHandlers    []HandlerFn
 	disconnectHandlers []HandlerFn
 	mrouter            *melody.Melody
+	streams            map[*melody.Session]*stream
 
-	lock sync.Mutex
+	lock        sync.Mutex
+	streamsLock sync.Mutex
 }
 
 func NewRouter(m *melody.Melody) *Router {
@@ -31,6 +34,7 @@ func NewRouter(m *melody.Melody) *Router {
 		connectHandlers:    []HandlerFn{},
 		disconnectHandlers: []HandlerFn{},
 		mrouter:            m,
+		streams:            map[*melody.Session]*stream{},
 	}
 }
 
@@ -38,7 +42,12 @@ func (r *Router) Handler(s *melody.Session, msg []byte) {
 	r.lock.Lock()
 	defer r.lock.Unlock()
 
-	session, err := newSession(msg, s, r.mrouter)
+	st := r.stream(s)
+	if st != nil {
+		atomic.AddUint64(&st.received, 1)
+	}
+
+	session, err := newSession(msg, s, r, st)
 	if err != nil {
 		session.Error("internal", err)
 		return
@@ -66,6 +75,17 @@ func (r *Router) Handler(s *melody.Session, msg []byte) {
 	s.Write([]byte(err.Error()))
 }
 
+// Broadcast sends msg to every connected client
+func (r *Router) Broadcast(msg []byte) error {
+	if err := r.mrouter.Broadcast(msg); err != nil {
+		return err
+	}
+
+	r.countSent(nil)
+
+	return nil
+}
+
 func (r *Router) HandleConnect(s *melody.Session) {
 	r.lock.Lock()
 	defer r.lock.Unlock()
@@ -75,7 +95,9 @@ func (r *Router) HandleConnect(s *melody.Session) {
 	session := Session{
 		msession: s,
 		mrouter:  r.mrouter,
+		router:   r,
 		Request:  s.Request,
+		stream:   r.addStream(s),
 	}
 
 	var err error
@@ -102,9 +124,12 @@ func (r *Router) HandleDisconnect(s *melody.Session) {
 	r.lock.Lock()
 	defer r.lock.Unlock()
 
+	defer r.removeStream(s)
+
 	session := Session{
 		msession: s,
 		mrouter:  r.mrouter,
+		router:   r,
 		Request:  s.Request,
 	}
 
@@ -0,0 +1,96 @@
+package websocket
+
+import (
+	"sort"
+	"sync/atomic"
+	"time"
+
+	"gopkg.in/olahol/melody.v1"
+)
+
+// Stream describes a connected websocket client. Sent counts replies and
+// broadcasts sent to the client.
+type Stream struct {
+	RemoteAddr  string    `json:"remote_addr"`
+	ConnectedAt time.Time `json:"connected_at"`
+	Received    uint64    `json:"received"`
+	Sent        uint64    `json:"sent"`
+}
+
+// counters are kept first for 64-bit alignment of atomic operations on 32-bit
+// platforms
+type stream struct {
+	received    uint64
+	sent        uint64
+	remoteAddr  string
+	connectedAt time.Time
+}
+
+func newStream(s *melody.Session) *stream {
+	return &stream{
+		remoteAddr:  s.Request.RemoteAddr,
+		connectedAt: time.Now(),
+	}
+}
+
+func (s *stream) snapshot() Stream {
+	return Stream{
+		RemoteAddr:  s.remoteAddr,
+		ConnectedAt: s.connectedAt,
+		Received:    atomic.LoadUint64(&s.received),
+		Sent:        atomic.LoadUint64(&s.sent),
+	}
+}
+
+// Streams returns the currently connected clients, oldest first
+func (r *Router) Streams() []Stream {
+	r.streamsLock.Lock()
+	defer r.streamsLock.Unlock()
+
+	res := make([]Stream, 0, len(r.streams))
+	for _, s := range r.streams {
+		res = append(res, s.snapshot())
+	}
+
+	sort.Slice(res, func(i, j int) bool {
+		return res[i].ConnectedAt.Before(res[j].ConnectedAt)
+	})
+
+	return res
+}
+
+func (r *Router) stream(s *melody.Session) *stream {
+	r.streamsLock.Lock()
+	defer r.streamsLock.Unlock()
+
+	return r.streams[s]
+}
+
+func (r *Router) addStream(s *melody.Session) *stream {
+	r.streamsLock.Lock()
+	defer r.streamsLock.Unlock()
+
+	st := newStream(s)
+	r.streams[s] = st
+
+	return st
+}
+
+func (r *Router) removeStream(s *melody.Session) {
+	r.streamsLock.Lock()
+	defer r.streamsLock.Unlock()
+
+	delete(r.streams, s)
+}
+
+// countSent counts a message sent to every stream except the one of exclude
+func (r *Router) countSent(exclude *melody.Session) {
+	r.streamsLock.Lock()
+	defer r.streamsLock.Unlock()
+
+	for s, st := range r.streams {
+		if s != exclude {
+			atomic.AddUint64(&st.sent, 1)
+		}
+	}
+}
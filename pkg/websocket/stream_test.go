@@ -0,0 +1,46 @@
+package websocket_test
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/JacobSoderblom/krypin/pkg/websocket"
+	gorilla "github.com/gorilla/websocket"
+	"github.com/stretchr/testify/assert"
+	"gopkg.in/olahol/melody.v1"
+)
+
+func TestStreamsTracksConnections(t *testing.T) {
+	assert := assert.New(t)
+
+	m := melody.New()
+	router := websocket.NewRouter(m)
+
+	m.HandleMessage(router.Handler)
+	m.HandleConnect(router.HandleConnect)
+	m.HandleDisconnect(router.HandleDisconnect)
+
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		m.HandleRequest(w, r)
+	}))
+	defer server.Close()
+
+	conn, _, err := gorilla.DefaultDialer.Dial("ws"+strings.TrimPrefix(server.URL, "http"), nil)
+	assert.Nil(err)
+
+	assert.Eventually(func() bool {
+		return len(router.Streams()) == 1
+	}, time.Second, 10*time.Millisecond)
+
+	assert.Nil(router.Broadcast([]byte(`{"topic":"devices/discovered"}`)))
+	assert.Equal(uint64(1), router.Streams()[0].Sent)
+
+	conn.Close()
+
+	assert.Eventually(func() bool {
+		return len(router.Streams()) == 0
+	}, time.Second, 10*time.Millisecond)
+}